@@ -23,9 +23,72 @@ pub enum HelperError {
     /// Error when supplied arguments do not match an arity range.
     #[error("Helper '{0}' got invalid arity expects {1}-{2} argument(s)")]
     ArityRange(String, usize, usize),
+    /// Error when fewer arguments are supplied than a minimum arity.
+    #[error(
+        "Helper '{0}' got invalid arity expects at least {1} argument(s)"
+    )]
+    ArityMin(String, usize),
     /// Error when a helper expects a string argument.
     #[error("Helper '{0}' got invalid argument at index {1}, string expected")]
     ArgumentTypeString(String, usize),
+    /// Error when a helper expects an argument to be a
+    /// non-negative integer.
+    #[error("Helper '{0}' got invalid argument at index {1}, non-negative integer expected")]
+    ArgumentTypeUnsigned(String, usize),
+    /// Error when a helper expects an argument to be a
+    /// non-negative number.
+    #[error("Helper '{0}' got invalid argument at index {1}, non-negative number expected")]
+    ArgumentTypeNonNegative(String, usize),
+    /// Error when a helper expects an argument to be a
+    /// positive integer.
+    #[error("Helper '{0}' got invalid argument at index {1}, positive integer expected")]
+    ArgumentTypePositive(String, usize),
+    /// Error when an argument in a list of arguments is not one
+    /// of the expected types.
+    #[error("Helper '{0}' got invalid argument at index {1}, expected '{2}' but got '{3}'")]
+    ArgumentType(String, usize, String, String),
+    /// Error when a helper expects an argument to be an integer.
+    #[error(
+        "Helper '{0}' got invalid argument at index {1}, integer expected"
+    )]
+    ArgumentTypeInteger(String, usize),
+    /// Error when a numerical argument exceeds the maximum allowed value.
+    #[error(
+        "Helper '{0}' got invalid argument at index {1}, maximum value is {2}"
+    )]
+    ArgumentLimit(String, usize, u64),
+    /// Error when the step for a range is zero.
+    #[error("Helper '{0}' got invalid step, step must not be zero")]
+    RangeStepZero(String),
+    /// Error when a range would exceed the maximum length.
+    #[error("Helper '{0}' got invalid range, maximum length is {1}")]
+    RangeLimit(String, u64),
+    /// Error when a repeated string would exceed the maximum length.
+    #[error("Helper '{0}' got invalid arguments, maximum length is {1}")]
+    RepeatLimit(String, u64),
+    /// Error when a helper expects a hash parameter to be a
+    /// non-negative integer.
+    #[error("Helper '{0}' got invalid hash parameter '{1}', non-negative integer expected")]
+    ParameterTypeUnsigned(String, String),
+    /// Error when a numerical hash parameter exceeds the maximum
+    /// allowed value.
+    #[error(
+        "Helper '{0}' got invalid hash parameter '{1}', maximum value is {2}"
+    )]
+    ParameterLimit(String, String, u64),
+    /// Error when a helper expects a hash parameter to be an
+    /// array of strings.
+    #[error("Helper '{0}' got invalid hash parameter '{1}', array of strings expected")]
+    ParameterTypeStringArray(String, String),
+    /// Error when a hash parameter is not one of the accepted values.
+    #[error(
+        "Helper '{0}' got invalid hash parameter '{1}', expected one of {2}"
+    )]
+    ParameterValue(String, String, String),
+    /// Error when sorting by value encounters a value that is
+    /// not a scalar.
+    #[error("Helper '{0}' can only sort scalar values")]
+    SortScalarExpected(String),
 
     /*
     /// Error when a helper expects an iterable (object or array).
@@ -46,6 +109,13 @@ pub enum HelperError {
     )]
     TypeAssert(String, String, String),
 
+    /// Error when a type assertion fails for a helper call
+    /// with the template name and line number of the call.
+    #[error(
+        "Helper '{0}' type assertion failed, expected '{1}' but got '{2}' at {3}:{4}"
+    )]
+    TypeAssertAt(String, String, String, String, usize),
+
     /// Proxy for syntax errors that occur via helpers.
     ///
     /// For example when dynamically evaluating paths passed to
@@ -9,7 +9,7 @@ pub mod syntax;
 
 pub use helper::HelperError;
 pub use render::RenderError;
-pub use source::{ErrorInfo, SourcePos};
+pub use source::{ErrorInfo, SourceContext, SourcePos, SourceSnippet};
 pub use syntax::SyntaxError;
 
 /// Generic error type that wraps more specific types and is
@@ -25,17 +25,38 @@ pub enum Error {
     /// Error when a named template does not exist.
     #[error("Template not found '{0}'")]
     TemplateNotFound(String),
+    /// Error when custom delimiters are invalid.
+    #[error("Delimiters must be two bytes, got '{0}' and '{1}'")]
+    InvalidDelimiters(String, String),
     /// Proxy IO errors.
     #[error(transparent)]
     Io(#[from] IoError),
 }
 
+impl Error {
+    /// Get the source code context when this is a syntax error.
+    ///
+    /// Includes syntax errors raised by helpers during rendering,
+    /// for example when evaluating a path.
+    pub fn source_context(&self) -> Option<&SourceContext> {
+        match *self {
+            Self::Syntax(ref e) => e.source_context(),
+            Self::Render(RenderError::Helper(HelperError::Syntax(ref e))) => {
+                e.source_context()
+            }
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::Syntax(ref e) => fmt::Debug::fmt(e, f),
             Self::Render(ref e) => fmt::Debug::fmt(e, f),
-            Self::TemplateNotFound(_) => fmt::Display::fmt(self, f),
+            Self::TemplateNotFound(_) | Self::InvalidDelimiters(_, _) => {
+                fmt::Display::fmt(self, f)
+            }
             Self::Io(ref e) => fmt::Debug::fmt(e, f),
         }
     }
@@ -10,14 +10,32 @@ pub enum RenderError {
     #[error("Partial '{0}' not found")]
     PartialNotFound(String),
 
+    /// Error when a partial name resolved from a sub-expression
+    /// could not be found.
+    #[error(
+        "Partial '{0}' not found, name was resolved from sub-expression '{1}'"
+    )]
+    DynamicPartialNotFound(String, String),
+
     /// Error when a variable could not be resolved.
     #[error("Variable '{0}' not found in {1}, check the variable path and verify the template data")]
     VariableNotFound(String, String),
 
+    /// Error when a path references more parent scopes than are available.
+    #[error(
+        "Path '{0}' references {1} parent scope(s) but only {2} available"
+    )]
+    ParentScopeOutOfRange(String, usize, usize),
+
     /// Error when a helper could not be found.
     #[error("Helper '{0}' not found, check the name")]
     HelperNotFound(String),
 
+    /// Informational lint diagnostic when a simple path is both
+    /// a registered helper and a field of the sample data.
+    #[error("Helper '{0}' shadows the variable '{0}', use './{0}' or 'this.{0}' to reference the variable")]
+    HelperShadowsVariable(String),
+
     /// Error when evaluating a path and a syntax error occurs.
     ///
     /// Paths can be dynamically evaluated when the
@@ -26,6 +44,15 @@ pub enum RenderError {
     #[error("Syntax error while evaluating path '{0}'")]
     EvaluatePath(String),
 
+    /// Error when a decorator is not supported.
+    #[error("Decorator '{0}' not found, only 'inline' is supported")]
+    DecoratorNotFound(String),
+
+    /// Error when an inline partial is not a block with a single
+    /// string argument for the partial name.
+    #[error("Inline partial '{0}' must be a block with a string name")]
+    InlinePartial(String),
+
     /// Error when a cycle is detected whilst handling a partial.
     #[error("Cycle detected whilst processing partial '{0}'")]
     PartialCycle(String),
@@ -34,12 +61,28 @@ pub enum RenderError {
     #[error("Cycle detected whilst processing helper '{0}'")]
     HelperCycle(String),
 
-    /// Error when a partial is not a simple identifier.
-    #[error("Partial names must be simple identifiers, got path '{0}'")]
+    /// Error when the render budget is exhausted.
+    #[error("Render budget of {0} operations exceeded")]
+    BudgetExceeded(u64),
+
+    /// Error when a helper expansion exceeds the iteration limit.
+    #[error(
+        "Helper '{0}' expansion of {1} iterations exceeds the limit of {2}"
+    )]
+    IterationLimit(String, usize, usize),
+
+    /// Error when a partial name is not a path of identifiers.
+    #[error("Partial names must be identifiers, got path '{0}'")]
     PartialIdentifier(String),
     /// Error when a block is not a simple identifier.
     #[error("Block names must be simple identifiers, got path '{0}'")]
     BlockIdentifier(String),
+    /// Error when an expression to evaluate is not a single statement.
+    #[error("Expression '{0}' must be a single statement")]
+    InvalidExpression(String),
+    /// Error when a template is expected to be a single statement.
+    #[error("Template '{0}' must be a single statement")]
+    SingleExpression(String),
     /// Error attempting to invoke a sub-expression outside of a partial target context.
     #[error("Block target sub expressions are only supported for partials")]
     BlockTargetSubExpr,
@@ -52,6 +95,14 @@ pub enum RenderError {
     //#[error(transparent)]
     //Syntax(#[from] Box<SyntaxError>),
 
+    /// Error when writing to the output destination fails.
+    ///
+    /// Includes the number of bytes written before the failure so
+    /// callers can distinguish an output that has gone away, for
+    /// example, a disconnected client, from an error in a template.
+    #[error("Failed to write output after {1} byte(s), {0}")]
+    OutputWrite(#[source] IoError, u64),
+
     /// Proxy for IO errors.
     #[error(transparent)]
     Io(#[from] IoError),
@@ -80,6 +131,48 @@ impl PartialEq for RenderError {
             (Self::PartialNotFound(ref s), Self::PartialNotFound(ref o)) => {
                 s == o
             }
+            (
+                Self::DynamicPartialNotFound(ref s, ref s_expr),
+                Self::DynamicPartialNotFound(ref o, ref o_expr),
+            ) => s == o && s_expr == o_expr,
+            (Self::HelperNotFound(ref s), Self::HelperNotFound(ref o)) => {
+                s == o
+            }
+            (
+                Self::HelperShadowsVariable(ref s),
+                Self::HelperShadowsVariable(ref o),
+            ) => s == o,
+            (
+                Self::DecoratorNotFound(ref s),
+                Self::DecoratorNotFound(ref o),
+            ) => s == o,
+            (Self::InlinePartial(ref s), Self::InlinePartial(ref o)) => s == o,
+            (
+                Self::InvalidExpression(ref s),
+                Self::InvalidExpression(ref o),
+            ) => s == o,
+            (Self::SingleExpression(ref s), Self::SingleExpression(ref o)) => {
+                s == o
+            }
+            (
+                Self::ParentScopeOutOfRange(ref s, ref s_req, ref s_depth),
+                Self::ParentScopeOutOfRange(ref o, ref o_req, ref o_depth),
+            ) => s == o && s_req == o_req && s_depth == o_depth,
+            (Self::BudgetExceeded(ref s), Self::BudgetExceeded(ref o)) => {
+                s == o
+            }
+            (
+                Self::IterationLimit(ref s, ref s_count, ref s_limit),
+                Self::IterationLimit(ref o, ref o_count, ref o_limit),
+            ) => s == o && s_count == o_count && s_limit == o_limit,
+            (
+                Self::OutputWrite(ref s, ref s_written),
+                Self::OutputWrite(ref o, ref o_written),
+            ) => s == o && s_written == o_written,
+            (
+                Self::VariableNotFound(ref s, ref s_name),
+                Self::VariableNotFound(ref o, ref o_name),
+            ) => s == o && s_name == o_name,
             _ => false,
         }
     }
@@ -1,5 +1,5 @@
 //! Utilties for generating error messages with source code.
-use std::fmt;
+use std::{fmt, ops::Deref};
 use unicode_width::UnicodeWidthStr;
 
 use crate::parser::ParseState;
@@ -52,11 +52,11 @@ impl<'source> ErrorInfo<'source> {
     }
 
     fn find_prev_line_offset(&self, s: &str, pos: &SourcePos) -> Option<usize> {
-        let mut counter: usize = pos.byte_offset().clone();
+        // Clamp so errors at the end of the source are handled
+        let mut counter: usize =
+            std::cmp::min(*pos.byte_offset(), s.len().saturating_sub(1));
         while counter > 0 {
-            // TODO: clamp end range to string length!
-            let slice = &s[counter..counter + 1];
-            if slice == "\n" {
+            if s.as_bytes()[counter] == b'\n' {
                 return Some(counter);
             }
             counter -= 1;
@@ -65,11 +65,9 @@ impl<'source> ErrorInfo<'source> {
     }
 
     fn find_next_line_offset(&self, s: &str, pos: &SourcePos) -> Option<usize> {
-        let mut counter: usize = pos.byte_offset().clone();
+        let mut counter: usize = *pos.byte_offset();
         while counter < s.len() {
-            // TODO: clamp end range to string length!
-            let slice = &s[counter..counter + 1];
-            if slice == "\n" {
+            if s.as_bytes()[counter] == b'\n' {
                 return Some(counter);
             }
             counter += 1;
@@ -102,39 +100,106 @@ impl<'source> From<(&'source str, &mut ParseState, Vec<String>)>
     }
 }
 
-impl fmt::Debug for ErrorInfo<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = self.source;
-        let pos = &self.source_pos;
-        let prev_line = self.find_prev_line_offset(s, pos);
+/// Source code context for a syntax error.
+///
+/// Provides the position, source line and notes so that tools can
+/// generate their own diagnostics; the `Display` implementation
+/// formats the context as the source code snippet used by
+/// syntax errors.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SourceContext {
+    file_name: String,
+    line: usize,
+    column: usize,
+    byte_offset: usize,
+    source_line: String,
+    notes: Vec<String>,
+}
+
+impl SourceContext {
+    /// The file name given when the template was compiled.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// The line number, starting at one.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column number, starting at one; columns are counted
+    /// using the display width of the characters.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte offset of the error in the template source.
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// The line of source code that contains the error.
+    pub fn source_line(&self) -> &str {
+        &self.source_line
+    }
+
+    /// Additional notes for the error.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+}
+
+impl From<&ErrorInfo<'_>> for SourceContext {
+    fn from(info: &ErrorInfo<'_>) -> Self {
+        let s = info.source;
+        let pos = &info.source_pos;
+        let prev_line = info.find_prev_line_offset(s, pos);
         let prev_line_offset = if let Some(offset) = prev_line {
             offset + 1
         } else {
             0
         };
 
-        let next_line = self.find_next_line_offset(s, pos);
+        let next_line = info.find_next_line_offset(s, pos);
         let next_line_offset = if let Some(offset) = next_line {
             offset
         } else {
             s.len()
         };
 
-        let line_slice = &s[prev_line_offset..next_line_offset];
-        let line_number = pos.line();
-
-        let line_prefix = format!(" {} | ", line_number + 1);
-        let line_padding = " ".repeat(line_prefix.len() - 3);
+        // Exclude the carriage return for CRLF line endings
+        let line_slice =
+            s[prev_line_offset..next_line_offset].trim_end_matches('\r');
 
         let diff = (pos.byte_offset() - prev_line_offset) + 1;
         let diff_start = prev_line_offset;
-        let diff_end = prev_line_offset + diff;
+        let diff_end = std::cmp::min(prev_line_offset + diff, s.len());
         let diff_str = &s[diff_start..diff_end];
 
-        let cols = UnicodeWidthStr::width(diff_str);
+        Self {
+            file_name: info.file_name.clone(),
+            line: pos.line() + 1,
+            column: UnicodeWidthStr::width(diff_str),
+            byte_offset: *pos.byte_offset(),
+            source_line: line_slice.to_string(),
+            notes: info.notes.clone(),
+        }
+    }
+}
+
+impl From<ErrorInfo<'_>> for SourceContext {
+    fn from(info: ErrorInfo<'_>) -> Self {
+        SourceContext::from(&info)
+    }
+}
+
+impl fmt::Display for SourceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_prefix = format!(" {} | ", self.line);
+        let line_padding = " ".repeat(line_prefix.len() - 3);
 
-        let file_info =
-            format!("{}:{}:{}", self.file_name, line_number + 1, cols);
+        let cols = self.column;
+        let file_info = format!("{}:{}:{}", self.file_name, self.line, cols);
 
         let err_pointer: String = if cols > 0 {
             format!("{}^", "-".repeat(cols - 1))
@@ -142,23 +207,81 @@ impl fmt::Debug for ErrorInfo<'_> {
             "^".to_string()
         };
 
-        //write!(f, "error: {}\n", self.to_string())?;
         write!(f, "{}--> {}\n", line_padding, file_info)?;
         write!(f, "{} |\n", line_padding)?;
-        write!(f, "{}{}\n", line_prefix, line_slice)?;
+        write!(f, "{}{}\n", line_prefix, self.source_line)?;
         write!(f, "{} | {}", line_padding, err_pointer)?;
 
-        if !self.notes.is_empty() {
-            write!(f, "\n")?;
-            for n in self.notes.iter() {
-                write!(f, "{} = note: {}", line_padding, n)?;
-            }
+        for n in self.notes.iter() {
+            write!(f, "\n{} = note: {}", line_padding, n)?;
         }
 
         Ok(())
     }
 }
 
+/// Source code snippet for a syntax error.
+///
+/// Dereferences to the formatted snippet text; snippets created
+/// by the parser also keep the structured source context.
+#[derive(Clone, Eq, PartialEq)]
+pub struct SourceSnippet {
+    text: String,
+    context: Option<Box<SourceContext>>,
+}
+
+impl SourceSnippet {
+    /// The source context when the snippet was created by the parser.
+    pub fn context(&self) -> Option<&SourceContext> {
+        self.context.as_deref()
+    }
+}
+
+impl Deref for SourceSnippet {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.text
+    }
+}
+
+impl From<String> for SourceSnippet {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            context: None,
+        }
+    }
+}
+
+impl From<ErrorInfo<'_>> for SourceSnippet {
+    fn from(info: ErrorInfo<'_>) -> Self {
+        let context = SourceContext::from(&info);
+        Self {
+            text: context.to_string(),
+            context: Some(Box::new(context)),
+        }
+    }
+}
+
+impl fmt::Display for SourceSnippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.text, f)
+    }
+}
+
+impl fmt::Debug for SourceSnippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.text, f)
+    }
+}
+
+impl fmt::Debug for ErrorInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&SourceContext::from(self), f)
+    }
+}
+
 impl Into<String> for ErrorInfo<'_> {
     fn into(self) -> String {
         format!("{:?}", self)
@@ -2,34 +2,40 @@
 use std::fmt;
 use thiserror::Error;
 
+use crate::error::{SourceContext, SourceSnippet};
+
 /// Errors generated when compiling a template.
 #[derive(Error, Eq, PartialEq)]
 pub enum SyntaxError {
     /// Error when an identifier is expected.
     #[error("Syntax error, expecting identifier")]
-    ExpectedIdentifier(String),
+    ExpectedIdentifier(SourceSnippet),
 
     /// Error when a path is expected.
     #[error("Syntax error, expecting path")]
-    ExpectedPath(String),
+    ExpectedPath(SourceSnippet),
 
     /// Error if a block name is not a simple identifier.
     #[error("Syntax error, block name must be an identifier")]
-    BlockName(String),
+    BlockName(SourceSnippet),
 
     /// Error when a newline is enccountered in a raw literal.
     #[error("Syntax error, new lines in raw literals must be escaped (\\n)")]
-    LiteralNewline(String),
+    LiteralNewline(SourceSnippet),
 
     /// Error when the partial operator is not the first token in a call statement.
     #[error("Syntax error, partial operator (>) must come first")]
-    PartialPosition(String),
+    PartialPosition(SourceSnippet),
+
+    /// Error when the decorator operator is not the first token in a call statement.
+    #[error("Syntax error, decorator operator (*) must come first")]
+    DecoratorPosition(SourceSnippet),
 
     /// Error when a sub-expression is closed by no sub-expression is open.
     #[error(
         "Syntax error, got close sub-expression but no sub-expression is open"
     )]
-    SubExprNotOpen(String),
+    SubExprNotOpen(SourceSnippet),
 
     /// Error when a sub-expression attempts to use a sub-expression for it's target.
     ///
@@ -38,146 +44,173 @@ pub enum SyntaxError {
     #[error(
         "Syntax error, sub-expression must use an identifier for the target"
     )]
-    SubExprTargetNotAllowed(String),
+    SubExprTargetNotAllowed(SourceSnippet),
 
     /// Error when a path delimiter is encountered in an invalid position.
     #[error("Syntax error, path delimiter (.) not allowed here")]
-    PathDelimiterNotAllowed(String),
+    PathDelimiterNotAllowed(SourceSnippet),
 
     /// Error when the `else` keyword is encountered in an invalid position.
     #[error("Syntax error, 'else' keyword is not allowed here")]
-    ElseNotAllowed(String),
+    ElseNotAllowed(SourceSnippet),
 
     /// Error when the `this` keywords is not at the start of a path.
     #[error(
         "Syntax error, explicit this reference must be at the start of a path"
     )]
-    UnexpectedPathExplicitThis(String),
+    UnexpectedPathExplicitThis(SourceSnippet),
 
     /// Error when a parent path reference (../) is not at the start of a path.
     #[error("Syntax error, parent scopes must be at the start of a path")]
-    UnexpectedPathParent(String),
+    UnexpectedPathParent(SourceSnippet),
 
     /// Error when a local identifier is not at the start of a path.
     #[error(
         "Syntax error, local scope identifiers must be at the start of a path"
     )]
-    UnexpectedPathLocal(String),
+    UnexpectedPathLocal(SourceSnippet),
 
     /// Error when an identifier is expected but a path delimiter was encountered.
     #[error("Syntax error, expected identifier but got path delimiter")]
-    UnexpectedPathDelimiter(String),
+    UnexpectedPathDelimiter(SourceSnippet),
 
     /// Error when parent scope references and local identifiers are combined illegally.
     #[error("Syntax error, parent scopes and local identifiers are mutually exclusive")]
-    UnexpectedPathParentWithLocal(String),
+    UnexpectedPathParentWithLocal(SourceSnippet),
 
     /// Error attempting to mix parent scope references and explicit this.
     #[error(
         "Syntax error, parent scopes and explicit this are mutually exclusive"
     )]
-    UnexpectedPathParentWithExplicit(String),
+    UnexpectedPathParentWithExplicit(SourceSnippet),
 
     /// Error when a path delimiter is expected.
     #[error("Syntax error, expected path delimiter (.)")]
-    ExpectedPathDelimiter(String),
+    ExpectedPathDelimiter(SourceSnippet),
 
     /// Error when a sub-expression was not terminated.
     #[error("Syntax error, sub-expression not terminated")]
-    OpenSubExpression(String),
+    OpenSubExpression(SourceSnippet),
 
     /// Error when a closing tag name does not match the opening name.
     #[error("Syntax error, closing name does not match")]
-    TagNameMismatch(String),
+    TagNameMismatch(SourceSnippet),
+
+    /// Error when a hash parameter key is repeated in a call.
+    #[error("Syntax error, duplicate hash parameter key")]
+    DuplicateHashKey(SourceSnippet),
 
     /// Error when an end tag is encountered but no block is open.
     #[error("Syntax error, got a closing tag but no block is open")]
-    BlockNotOpen(String),
+    BlockNotOpen(SourceSnippet),
 
     /// Error when a sub-expression is not terminated.
     #[error("Syntax error, sub-expression was not terminated")]
-    SubExpressionNotTerminated(String),
+    SubExpressionNotTerminated(SourceSnippet),
     /// Erro when a link is not terminated.
     #[error("Syntax error, link was not terminated")]
-    LinkNotTerminated(String),
+    LinkNotTerminated(SourceSnippet),
 
     /// Error when the opening tag for a raw block is not terminated.
     #[error("Syntax error, raw block open tag was not terminated")]
-    RawBlockOpenNotTerminated(String),
+    RawBlockOpenNotTerminated(SourceSnippet),
 
     /// Error when a raw block is not terminated.
     #[error("Syntax error, raw block was not terminated")]
-    RawBlockNotTerminated(String),
+    RawBlockNotTerminated(SourceSnippet),
     /// Error when a raw comment is not terminated.
     #[error("Syntax error, raw comment was not terminated")]
-    RawCommentNotTerminated(String),
+    RawCommentNotTerminated(SourceSnippet),
     /// Error when a raw statement is not terminated.
     #[error("Syntax error, raw statement was not terminated")]
-    RawStatementNotTerminated(String),
+    RawStatementNotTerminated(SourceSnippet),
     /// Error when a comment is not terminated.
     #[error("Syntax error, comment was not terminated")]
-    CommentNotTerminated(String),
+    CommentNotTerminated(SourceSnippet),
+    /// Error when a comment contains a statement.
+    ///
+    /// This is a lint diagnostic; the comment still parses.
+    #[error("Syntax error, comments may not contain statements")]
+    CommentStatement(SourceSnippet),
 
     /// Error attempting to use a sub-expression outside of a partial target context.
     #[error("Syntax error, block target sub expressions are only supported for partials")]
-    BlockTargetSubExpr(String),
+    BlockTargetSubExpr(SourceSnippet),
     /// Error when an empty path is encountered.
     #[error("Syntax error, path is empty")]
-    EmptyPath(String),
+    EmptyPath(SourceSnippet),
     /// Error if we could not identify the type of a path component (internal error).
     #[error("Syntax error, path component type could not be identified")]
-    ComponentType(String),
+    ComponentType(SourceSnippet),
     /// Error attempting to combine partials with conditionals.
     #[error("Syntax error, partials and conditionals may not be combined")]
-    MixedPartialConditional(String),
+    MixedPartialConditional(SourceSnippet),
 
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected error token for context '{0}'")]
-    TokenError(String, String),
+    TokenError(String, SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting path or sub-expression for call target")]
-    TokenCallTarget(String),
+    TokenCallTarget(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting JSON literal token")]
-    TokenJsonLiteral(String),
+    TokenJsonLiteral(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting parameter token")]
-    TokenParameter(String),
+    TokenParameter(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting key/value token")]
-    TokenHashKeyValue(String),
+    TokenHashKeyValue(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting raw literal token")]
-    TokenRawLiteral(String),
+    TokenRawLiteral(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing quoted literal (\"\")")]
-    TokenDoubleQuoteLiteral(String),
+    TokenDoubleQuoteLiteral(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing quoted literal ('')")]
-    TokenSingleQuoteLiteral(String),
+    TokenSingleQuoteLiteral(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing quoted literal ([])")]
-    TokenArrayLiteral(String),
+    TokenArrayLiteral(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing link")]
-    TokenLink(String),
+    TokenLink(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing path")]
-    TokenParameterPath(String),
+    TokenParameterPath(SourceSnippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token, expecting end of raw block")]
-    TokenEndRawBlock(String),
+    TokenEndRawBlock(SourceSnippet),
 }
 
-impl fmt::Debug for SyntaxError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}\n", self.to_string())?;
+impl SyntaxError {
+    /// Get the source code context for this error.
+    ///
+    /// Returns `None` when the error was not created by the parser.
+    pub fn source_context(&self) -> Option<&SourceContext> {
+        self.snippet().context()
+    }
+
+    /// The line number of this error, starting at one.
+    pub fn line(&self) -> Option<usize> {
+        self.source_context().map(|context| context.line())
+    }
+
+    /// The column number of this error, starting at one.
+    pub fn column(&self) -> Option<usize> {
+        self.source_context().map(|context| context.column())
+    }
+
+    /// The source code snippet for this error.
+    pub fn snippet(&self) -> &SourceSnippet {
         match *self {
             Self::ExpectedIdentifier(ref source)
             | Self::ExpectedPath(ref source)
             | Self::BlockName(ref source)
             | Self::LiteralNewline(ref source)
             | Self::PartialPosition(ref source)
+            | Self::DecoratorPosition(ref source)
             | Self::SubExprNotOpen(ref source)
             | Self::SubExprTargetNotAllowed(ref source)
             | Self::PathDelimiterNotAllowed(ref source)
@@ -191,12 +224,14 @@ impl fmt::Debug for SyntaxError {
             | Self::ExpectedPathDelimiter(ref source)
             | Self::OpenSubExpression(ref source)
             | Self::TagNameMismatch(ref source)
+            | Self::DuplicateHashKey(ref source)
             | Self::SubExpressionNotTerminated(ref source)
             | Self::LinkNotTerminated(ref source)
             | Self::RawBlockNotTerminated(ref source)
             | Self::RawCommentNotTerminated(ref source)
             | Self::RawStatementNotTerminated(ref source)
             | Self::CommentNotTerminated(ref source)
+            | Self::CommentStatement(ref source)
             | Self::BlockTargetSubExpr(ref source)
             | Self::EmptyPath(ref source)
             | Self::ComponentType(ref source)
@@ -214,8 +249,14 @@ impl fmt::Debug for SyntaxError {
             | Self::TokenLink(ref source)
             | Self::TokenParameterPath(ref source)
             | Self::TokenEndRawBlock(ref source)
-            | Self::BlockNotOpen(ref source) => write!(f, "{}", source)?,
+            | Self::BlockNotOpen(ref source) => source,
         }
-        Ok(())
+    }
+}
+
+impl fmt::Debug for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n", self.to_string())?;
+        write!(f, "{}", self.snippet())
     }
 }
@@ -21,6 +21,53 @@ pub fn html(s: &str) -> String {
     output
 }
 
+/// Escape for HTML output and convert all non-ASCII characters
+/// to numeric character references, eg: `é` becomes `&#233;`.
+pub fn html_entities(s: &str) -> String {
+    let mut output = String::new();
+    for c in s.chars() {
+        match c {
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '&' => output.push_str("&amp;"),
+            '\'' => output.push_str("&#x27;"),
+            _ if !c.is_ascii() => output.push_str(&format!("&#{};", c as u32)),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+/// Escape for output inside a JavaScript string literal.
+///
+/// Quotes, backticks and backslashes are escaped with a backslash,
+/// control characters and the line terminators `U+2028` and `U+2029`
+/// use unicode escapes and angle brackets are escaped so that a value
+/// cannot close an inline `<script>` element.
+pub fn javascript(s: &str) -> String {
+    let mut output = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\'' => output.push_str("\\'"),
+            '`' => output.push_str("\\`"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '<' | '>' | '\u{2028}' | '\u{2029}' => {
+                output.push_str(&format!("\\u{:04X}", c as u32))
+            }
+            _ if c.is_control() => {
+                output.push_str(&format!("\\u{:04X}", c as u32))
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
 /// Do not escape output.
 pub fn noop(s: &str) -> String {
     s.to_owned()
@@ -0,0 +1,219 @@
+//! Helpers for arrays.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+
+use serde_json::{Number, Value};
+
+const INDEX: &str = "index";
+
+/// Maximum number of elements allowed in an array created
+/// by the `range` helper.
+pub const MAX_RANGE: u64 = 10_000;
+
+/// Get the array argument and optional count argument.
+fn arguments<'a>(
+    ctx: &'a Context<'_>,
+) -> Result<(&'a Vec<Value>, Option<usize>), HelperError> {
+    ctx.arity(1..2)?;
+    let list = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+    let count = if let Some(count) = ctx.get(1) {
+        Some(count.as_u64().ok_or_else(|| {
+            HelperError::ArgumentTypeUnsigned(ctx.name().to_string(), 1)
+        })? as usize)
+    } else {
+        None
+    };
+    Ok((list, count))
+}
+
+/// Get the first element of an array.
+///
+/// Accepts an array argument and returns the first element; when
+/// the array is empty no value is returned so the result is `null`:
+///
+/// ```ignore
+/// {{first posts}}
+/// ```
+///
+/// When a count argument is given an array of up to count
+/// elements from the start of the array is returned:
+///
+/// ```ignore
+/// {{#each (first posts 3)}}{{title}}{{/each}}
+/// ```
+pub struct First;
+
+impl Helper for First {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let (list, count) = arguments(ctx)?;
+        if let Some(count) = count {
+            Ok(Some(Value::Array(
+                list.iter().take(count).cloned().collect(),
+            )))
+        } else {
+            Ok(list.first().cloned())
+        }
+    }
+}
+
+/// Get the last element of an array.
+///
+/// Accepts an array argument and returns the last element; when
+/// the array is empty no value is returned so the result is `null`:
+///
+/// ```ignore
+/// {{last posts}}
+/// ```
+///
+/// When a count argument is given an array of up to count
+/// elements from the end of the array is returned in the
+/// original order.
+pub struct Last;
+
+impl Helper for Last {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let (list, count) = arguments(ctx)?;
+        if let Some(count) = count {
+            let start = list.len().saturating_sub(count);
+            Ok(Some(Value::Array(list[start..].to_vec())))
+        } else {
+            Ok(list.last().cloned())
+        }
+    }
+}
+
+/// Create an array of integers.
+///
+/// Accepts start and end integer arguments and an optional step
+/// and returns the integers from start up to but not including end;
+/// typically used as a sub-expression to iterate a range:
+///
+/// ```ignore
+/// {{#each (range 0 10 2)}}{{this}}{{/each}}
+/// ```
+///
+/// The step defaults to one and must not be zero; a negative step
+/// counts down from start to end. The range may contain no more
+/// than [MAX_RANGE](crate::helper::array::MAX_RANGE) elements.
+pub struct Range;
+
+impl Range {
+    fn integer(
+        &self,
+        ctx: &Context<'_>,
+        index: usize,
+    ) -> Result<i64, HelperError> {
+        ctx.get(index).and_then(|v| v.as_i64()).ok_or_else(|| {
+            HelperError::ArgumentTypeInteger(ctx.name().to_string(), index)
+        })
+    }
+}
+
+impl Helper for Range {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..3)?;
+        let start = self.integer(ctx, 0)?;
+        let end = self.integer(ctx, 1)?;
+        let step = if ctx.get(2).is_some() {
+            self.integer(ctx, 2)?
+        } else {
+            1
+        };
+
+        if step == 0 {
+            return Err(HelperError::RangeStepZero(ctx.name().to_string()));
+        }
+
+        let distance = if step > 0 {
+            (end as i128 - start as i128).max(0)
+        } else {
+            (start as i128 - end as i128).max(0)
+        };
+        let step_size = (step as i128).abs();
+        let length = (distance + step_size - 1) / step_size;
+        if length > MAX_RANGE as i128 {
+            return Err(HelperError::RangeLimit(
+                ctx.name().to_string(),
+                MAX_RANGE,
+            ));
+        }
+        rc.assert_iterations(ctx.name(), length as usize)?;
+
+        let list = (0..length as i64)
+            .map(|index| Value::from(start + index * step))
+            .collect();
+        Ok(Some(Value::Array(list)))
+    }
+}
+
+/// Iterate an array in chunks.
+///
+/// Block helper that accepts an array argument and a positive
+/// integer size; the array is split into chunks of up to size
+/// elements and the inner template is rendered once for each chunk
+/// with the chunk array as the scope and the `@index` local variable
+/// set to the zero-based chunk index. The last chunk may be shorter:
+///
+/// ```ignore
+/// {{#chunk items 3}}<div>{{#each this}}{{name}}{{/each}}</div>{{/chunk}}
+/// ```
+///
+/// When the array is empty the `else` branch is rendered if present.
+pub struct Chunk;
+
+impl Helper for Chunk {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+        let template = ctx.assert_block(template)?;
+        let list = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+        let size = ctx
+            .get(1)
+            .and_then(|v| v.as_u64())
+            .filter(|size| *size > 0)
+            .ok_or_else(|| {
+                HelperError::ArgumentTypePositive(ctx.name().to_string(), 1)
+            })?;
+
+        if list.is_empty() {
+            rc.render_inverse(template)?;
+            return Ok(None);
+        }
+        let size = size as usize;
+        rc.assert_iterations(ctx.name(), list.len().div_ceil(size))?;
+
+        rc.push_scope(Scope::new());
+        for (index, chunk) in list.chunks(size).enumerate() {
+            if let Some(ref mut scope) = rc.scope_mut() {
+                scope.set_local(INDEX, Value::Number(Number::from(index)));
+                scope.set_base_value(Value::Array(chunk.to_vec()));
+            }
+            rc.template(template)?;
+        }
+        rc.pop_scope();
+        Ok(None)
+    }
+}
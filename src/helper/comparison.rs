@@ -1,9 +1,39 @@
-//! Helpers for numerical comparisons.
+//! Helpers for comparing numbers, strings and arrays.
 //!
-//! Arguments must be numerical values otherwise a type assertion
-//! error is returned.
+//! Both arguments must be the same type otherwise a type assertion
+//! error is returned; the ordering rules for each type are:
 //!
-//! Values are compared as `f64`.
+//! * Numbers: when both values are integers they are compared exactly;
+//!   when either value is a float both values are compared as `f64` so
+//!   that `eq 1 1.0` is true.
+//! * Strings: compared lexicographically by byte value so that
+//!   `gt "b" "a"` is true; no locale specific collation is performed.
+//! * Arrays: ordered by length so `gt` is true when the first array
+//!   has more items than the second.
+//!
+//! The `eq` and `ne` helpers accept arguments of any type; numbers
+//! are compared using the rules above, values of different types are
+//! never equal and all other values (including arrays and objects)
+//! are compared by value using JSON equality so `eq` is only true for
+//! arrays with equal items in the same order. Pass `strict=false` to
+//! compare loosely, in which case operands are coerced before
+//! comparison:
+//!
+//! * Number and string: the string is parsed as a JSON number and
+//!   compared using the number rules above, so `eq "1" 1` and
+//!   `eq "1.0" 1` are true; a string that is not a valid JSON number
+//!   (including one with surrounding whitespace) is never equal.
+//! * Boolean and string: the string `"true"` equals `true` and the
+//!   string `"false"` equals `false`; any other string is never equal.
+//!
+//! All other combinations are compared as in strict mode.
+//!
+//! When `eq` is given more than two arguments it is true if the first
+//! argument equals any of the other arguments so that `eq x 1 2 3` is
+//! an in-set check; likewise `ne` is true if the first argument is not
+//! equal to all of the other arguments.
+use std::cmp::Ordering;
+
 use crate::{
     error::HelperError,
     helper::{Helper, HelperValue},
@@ -11,32 +41,117 @@ use crate::{
     render::{Context, Render, Type},
 };
 
-use serde_json::Value;
+use serde_json::{Number, Value};
+
+const STRICT: &str = "strict";
+
+fn integer(num: &Number) -> Option<i128> {
+    num.as_i64()
+        .map(i128::from)
+        .or_else(|| num.as_u64().map(i128::from))
+}
+
+/// Order numbers exactly when both are integers otherwise as `f64`.
+pub(crate) fn ordering(lhs: &Number, rhs: &Number) -> Option<Ordering> {
+    if let (Some(lhs), Some(rhs)) = (integer(lhs), integer(rhs)) {
+        Some(lhs.cmp(&rhs))
+    } else if let (Some(lhs), Some(rhs)) = (lhs.as_f64(), rhs.as_f64()) {
+        lhs.partial_cmp(&rhs)
+    } else {
+        None
+    }
+}
+
+fn compare<'call>(
+    ctx: &Context<'call>,
+    lhs: &Value,
+    rhs: &Value,
+) -> Result<Ordering, HelperError> {
+    match (lhs, rhs) {
+        (Value::Number(lhs), Value::Number(rhs)) => ordering(lhs, rhs)
+            .ok_or_else(|| {
+                HelperError::InvalidNumericalOperand(ctx.name().to_string())
+            }),
+        (Value::String(lhs), Value::String(rhs)) => Ok(lhs.cmp(rhs)),
+        (Value::Array(lhs), Value::Array(rhs)) => Ok(lhs.len().cmp(&rhs.len())),
+        _ => Err(HelperError::InvalidNumericalOperand(ctx.name().to_string())),
+    }
+}
 
 fn cmp<'call, F>(ctx: &Context<'call>, cmp: F) -> HelperValue
 where
-    F: FnOnce(f64, f64) -> bool,
+    F: FnOnce(Ordering) -> bool,
 {
     ctx.arity(2..2)?;
 
-    let lhs = ctx.try_get(0, &[Type::Number])?;
-    let rhs = ctx.try_get(1, &[Type::Number])?;
+    let lhs = ctx.try_get(0, &[Type::Number, Type::String, Type::Array])?;
+    let rhs = ctx.try_get(1, &[Type::from(lhs)])?;
+    Ok(Some(Value::Bool(cmp(compare(ctx, lhs, rhs)?))))
+}
 
+/// Compare values of different types after coercion, returns `None`
+/// when the types cannot be coerced.
+fn coerce_equal(lhs: &Value, rhs: &Value) -> Option<bool> {
     match (lhs, rhs) {
-        (Value::Number(lhs), Value::Number(rhs)) => {
-            if let (Some(lhs), Some(rhs)) = (lhs.as_f64(), rhs.as_f64()) {
-                Ok(Some(Value::Bool(cmp(lhs, rhs))))
-            } else {
-                Err(HelperError::InvalidNumericalOperand(
-                    ctx.name().to_string(),
-                ))
-            }
+        (Value::Number(num), Value::String(s))
+        | (Value::String(s), Value::Number(num)) => Some(
+            s.parse::<Number>()
+                .ok()
+                .and_then(|other| ordering(num, &other))
+                == Some(Ordering::Equal),
+        ),
+        (Value::Bool(b), Value::String(s))
+        | (Value::String(s), Value::Bool(b)) => Some(match s.as_str() {
+            "true" => *b,
+            "false" => !*b,
+            _ => false,
+        }),
+        _ => None,
+    }
+}
+
+/// Determine if the first argument is equal to any of the
+/// other arguments.
+fn equal<'call>(ctx: &Context<'call>) -> Result<bool, HelperError> {
+    ctx.arity_min(2)?;
+
+    let strict = ctx
+        .try_param(STRICT, &[Type::Bool, Type::Null])?
+        .as_bool()
+        .unwrap_or(true);
+    let lhs = ctx.get(0).unwrap_or(&Value::Null);
+    for rhs in ctx.arguments().iter().skip(1) {
+        if equal_value(ctx, lhs, rhs, strict)? {
+            return Ok(true);
         }
-        _ => Err(HelperError::InvalidNumericalOperand(ctx.name().to_string())),
+    }
+    Ok(false)
+}
+
+fn equal_value<'call>(
+    ctx: &Context<'call>,
+    lhs: &Value,
+    rhs: &Value,
+    strict: bool,
+) -> Result<bool, HelperError> {
+    if !strict {
+        if let Some(result) = coerce_equal(lhs, rhs) {
+            return Ok(result);
+        }
+    }
+
+    match (lhs, rhs) {
+        (Value::Number(_), Value::Number(_)) => {
+            Ok(compare(ctx, lhs, rhs)? == Ordering::Equal)
+        }
+        _ => Ok(lhs == rhs),
     }
 }
 
 /// Perform an equality comparison.
+///
+/// Accepts two or more arguments and the `strict` hash parameter,
+/// see the module documentation.
 pub struct Equal;
 
 impl Helper for Equal {
@@ -46,11 +161,14 @@ impl Helper for Equal {
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        cmp(ctx, |lhs: f64, rhs: f64| lhs == rhs)
+        Ok(Some(Value::Bool(equal(ctx)?)))
     }
 }
 
 /// Perform a negated equality comparison.
+///
+/// Accepts two or more arguments and the `strict` hash parameter,
+/// see the module documentation.
 pub struct NotEqual;
 
 impl Helper for NotEqual {
@@ -60,11 +178,11 @@ impl Helper for NotEqual {
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        cmp(ctx, |lhs: f64, rhs: f64| lhs != rhs)
+        Ok(Some(Value::Bool(!equal(ctx)?)))
     }
 }
 
-/// Perform a numerical greater than comparison.
+/// Perform a greater than comparison.
 pub struct GreaterThan;
 
 impl Helper for GreaterThan {
@@ -74,11 +192,11 @@ impl Helper for GreaterThan {
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        cmp(ctx, |lhs: f64, rhs: f64| lhs > rhs)
+        cmp(ctx, |o| o == Ordering::Greater)
     }
 }
 
-/// Perform a numerical greater than or equal comparison.
+/// Perform a greater than or equal comparison.
 pub struct GreaterThanEqual;
 
 impl Helper for GreaterThanEqual {
@@ -88,11 +206,11 @@ impl Helper for GreaterThanEqual {
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        cmp(ctx, |lhs: f64, rhs: f64| lhs >= rhs)
+        cmp(ctx, |o| o != Ordering::Less)
     }
 }
 
-/// Perform a numerical less than comparison.
+/// Perform a less than comparison.
 pub struct LessThan;
 
 impl Helper for LessThan {
@@ -102,11 +220,11 @@ impl Helper for LessThan {
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        cmp(ctx, |lhs: f64, rhs: f64| lhs < rhs)
+        cmp(ctx, |o| o == Ordering::Less)
     }
 }
 
-/// Perform a numerical less than comparison.
+/// Perform a less than or equal comparison.
 pub struct LessThanEqual;
 
 impl Helper for LessThanEqual {
@@ -116,6 +234,6 @@ impl Helper for LessThanEqual {
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        cmp(ctx, |lhs: f64, rhs: f64| lhs <= rhs)
+        cmp(ctx, |o| o != Ordering::Greater)
     }
 }
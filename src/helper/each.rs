@@ -1,18 +1,25 @@
 //! Block helper that iterates arrays and objects.
 use crate::{
-    //error::HelperError,
-    helper::{Helper, HelperValue},
+    error::HelperError,
+    helper::{comparison::ordering, Helper, HelperValue},
     parser::ast::Node,
     render::{Context, Render, Scope},
 };
 
 use serde_json::{Number, Value};
+use std::cmp::Ordering;
 
 const FIRST: &str = "first";
 const LAST: &str = "last";
 const KEY: &str = "key";
 const INDEX: &str = "index";
 
+const REVERSE: &str = "reverse";
+const LIMIT: &str = "limit";
+const SORT: &str = "sort";
+const SORT_KEY: &str = "key";
+const SORT_VALUE: &str = "value";
+
 /// Iterate an array or object.
 ///
 /// Accepts a single argument of the target to iterate, if the
@@ -22,6 +29,7 @@ const INDEX: &str = "index";
 ///
 /// * `@first`: If this is the first iteration `true`.
 /// * `@last`: If this is the last iteration `true`.
+/// * `@level`: The number of enclosing block helpers, zero at the root.
 ///
 /// Note that these variables are set even for objects where iteration order
 /// is not guaranteed which can be useful.
@@ -29,6 +37,32 @@ const INDEX: &str = "index";
 /// For objects the `@key` variable contains the name of the field; for
 /// arrays the `@index` variable contains the current zero-based index.
 ///
+/// The optional hash parameter `reverse` when *truthy* iterates
+/// back-to-front and the `limit` hash parameter (a non-negative integer)
+/// caps the number of iterations; when both are given the sequence is
+/// reversed before the limit is applied. The local variables always
+/// reflect the transformed sequence.
+///
+/// Object entries are visited in the order of the underlying map which
+/// is ordered by key unless the `preserve_order` feature is enabled; for
+/// reproducible output the `sort` hash parameter orders the items
+/// before iteration. Use `sort="key"` to order by field name or
+/// `sort="value"` to order by value, which requires every value to be a
+/// scalar; mixed types order as null, booleans, numbers then strings and
+/// equal values are ordered by key. Array elements are keyed by index so
+/// `sort="key"` keeps their order. Sorting is applied before `reverse`
+/// and `limit`.
+///
+/// When there are no items to iterate, including when the target is
+/// null or missing, the `else` branch is rendered if present.
+///
+/// Block parameters name the current item and its position; the second
+/// parameter is the index for arrays and the key for objects:
+///
+/// ```ignore
+/// {{#each items as |item index|}}{{index}}: {{item.name}}{{/each}}
+/// ```
+///
 pub struct Each;
 
 impl Helper for Each {
@@ -45,51 +79,103 @@ impl Helper for Each {
             let args = ctx.arguments();
             let target = args.get(0).unwrap();
 
-            rc.push_scope(Scope::new());
-            match target {
-                Value::Object(t) => {
-                    let mut it = t.into_iter().enumerate();
-                    let mut next_value = it.next();
-                    while let Some((index, (key, value))) = next_value {
-                        next_value = it.next();
-                        if let Some(ref mut scope) = rc.scope_mut() {
-                            scope.set_local(FIRST, Value::Bool(index == 0));
-                            scope.set_local(
-                                LAST,
-                                Value::Bool(next_value.is_none()),
-                            );
-                            scope.set_local(
-                                INDEX,
-                                Value::Number(Number::from(index)),
-                            );
-                            scope.set_local(KEY, Value::String(key.to_owned()));
-                            scope.set_base_value(value.clone());
-                        }
-                        rc.template(template)?;
+            let reverse = ctx
+                .is_truthy(ctx.param(REVERSE).unwrap_or(&Value::Bool(false)));
+            let limit = if let Some(limit) = ctx.param(LIMIT) {
+                Some(limit.as_u64().ok_or_else(|| {
+                    HelperError::ParameterTypeUnsigned(
+                        ctx.name().to_string(),
+                        LIMIT.to_string(),
+                    )
+                })? as usize)
+            } else {
+                None
+            };
+
+            let sort = if let Some(sort) = ctx.param(SORT) {
+                match sort.as_str() {
+                    Some(SORT_KEY) | Some(SORT_VALUE) => sort.as_str(),
+                    _ => {
+                        return Err(HelperError::ParameterValue(
+                            ctx.name().to_string(),
+                            SORT.to_string(),
+                            format!("'{}' or '{}'", SORT_KEY, SORT_VALUE),
+                        ))
                     }
                 }
+            } else {
+                None
+            };
+
+            let mut items: Vec<(Option<&String>, &Value)> = match target {
+                Value::Object(t) => {
+                    t.iter().map(|(key, value)| (Some(key), value)).collect()
+                }
                 Value::Array(t) => {
-                    let len = t.len();
-                    for (index, value) in t.into_iter().enumerate() {
-                        if let Some(ref mut scope) = rc.scope_mut() {
-                            scope.set_local(FIRST, Value::Bool(index == 0));
-                            scope
-                                .set_local(LAST, Value::Bool(index == len - 1));
-                            scope.set_local(
-                                INDEX,
-                                Value::Number(Number::from(index)),
-                            );
-                            scope.set_base_value(value.clone());
-                        }
-                        rc.template(template)?;
-                    }
+                    t.iter().map(|value| (None, value)).collect()
                 }
                 _ => {
                     //return Err(HelperError::IterableExpected(
                     //name.to_string(),
                     //0,
                     //))
+                    Vec::new()
+                }
+            };
+
+            match sort {
+                Some(SORT_KEY) => items.sort_by(|a, b| a.0.cmp(&b.0)),
+                Some(SORT_VALUE) => {
+                    if !items.iter().all(|(_, value)| is_scalar(value)) {
+                        return Err(HelperError::SortScalarExpected(
+                            ctx.name().to_string(),
+                        ));
+                    }
+                    items.sort_by(|a, b| {
+                        compare_scalar(a.1, b.1).then_with(|| a.0.cmp(&b.0))
+                    });
+                }
+                _ => {}
+            }
+
+            if reverse {
+                items.reverse();
+            }
+
+            if let Some(limit) = limit {
+                items.truncate(limit);
+            }
+            rc.assert_iterations(ctx.name(), items.len())?;
+
+            if items.is_empty() {
+                rc.render_inverse(template)?;
+                return Ok(None);
+            }
+
+            let params = ctx.call().block_params();
+            rc.push_scope(Scope::new());
+            let len = items.len();
+            for (index, (key, value)) in items.into_iter().enumerate() {
+                if let Some(ref mut scope) = rc.scope_mut() {
+                    scope.set_local(FIRST, Value::Bool(index == 0));
+                    scope.set_local(LAST, Value::Bool(index == len - 1));
+                    scope.set_local(INDEX, Value::Number(Number::from(index)));
+                    if let Some(key) = key {
+                        scope.set_local(KEY, Value::String(key.to_owned()));
+                    }
+                    if let Some(name) = params.first() {
+                        scope.set_block_param(name, value.clone());
+                    }
+                    if let Some(name) = params.get(1) {
+                        let position = match key {
+                            Some(key) => Value::String(key.to_owned()),
+                            None => Value::Number(Number::from(index)),
+                        };
+                        scope.set_block_param(name, position);
+                    }
+                    scope.set_base_value(value.clone());
                 }
+                rc.template(template)?;
             }
             rc.pop_scope();
         }
@@ -97,3 +183,27 @@ impl Helper for Each {
         Ok(None)
     }
 }
+
+fn is_scalar(value: &Value) -> bool {
+    !matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        _ => 3,
+    }
+}
+
+fn compare_scalar(lhs: &Value, rhs: &Value) -> Ordering {
+    match (lhs, rhs) {
+        (Value::Bool(lhs), Value::Bool(rhs)) => lhs.cmp(rhs),
+        (Value::Number(lhs), Value::Number(rhs)) => {
+            ordering(lhs, rhs).unwrap_or(Ordering::Equal)
+        }
+        (Value::String(lhs), Value::String(rhs)) => lhs.cmp(rhs),
+        _ => rank(lhs).cmp(&rank(rhs)),
+    }
+}
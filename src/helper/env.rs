@@ -0,0 +1,84 @@
+//! Helper for reading process environment variables.
+//!
+//! This helper is not a builtin; it requires the `env-helper`
+//! feature which is not enabled by default and must be added to
+//! a registry explicitly:
+//!
+//! ```ignore
+//! registry.helpers_mut().insert("env", Box::new(Env::new()));
+//! ```
+//!
+//! Environment variables frequently contain secrets such as access
+//! tokens and passwords; a template that can call this helper can
+//! write any variable it is allowed to read to the output. Never
+//! add this helper to a registry that renders templates from an
+//! untrusted source and prefer [with_allowed()](Env#method.with_allowed)
+//! to restrict the variables that may be read.
+use std::collections::HashSet;
+
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+use serde_json::Value;
+
+/// Read an environment variable.
+///
+/// Accepts a single string argument which is the name of the
+/// variable and returns the value; when the variable is not set,
+/// is not valid Unicode or is not allowed the value is `null`:
+///
+/// ```ignore
+/// {{env "CARGO_PKG_VERSION"}}
+/// ```
+#[derive(Default)]
+pub struct Env {
+    allowed: Option<HashSet<String>>,
+}
+
+impl Env {
+    /// Create a helper that may read any environment variable.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a helper that may only read the named environment variables.
+    pub fn with_allowed<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed: Some(names.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    fn is_allowed(&self, name: &str) -> bool {
+        self.allowed
+            .as_ref()
+            .map(|allowed| allowed.contains(name))
+            .unwrap_or(true)
+    }
+}
+
+impl Helper for Env {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        if !self.is_allowed(name) {
+            return Ok(Some(Value::Null));
+        }
+        Ok(Some(
+            std::env::var(name)
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+        ))
+    }
+}
@@ -7,6 +7,22 @@ use crate::{
 
 use serde_json::Value;
 
+const INCLUDE_ZERO: &str = "includeZero";
+
+/// Determine if the first argument is *truthy* respecting the
+/// `includeZero` hash parameter.
+pub(crate) fn condition(ctx: &Context<'_>) -> bool {
+    let value = ctx.get(0).unwrap();
+    if let Value::Number(_) = value {
+        let include_zero = ctx
+            .is_truthy(ctx.param(INCLUDE_ZERO).unwrap_or(&Value::Bool(false)));
+        if include_zero {
+            return true;
+        }
+    }
+    ctx.is_truthy(value)
+}
+
 /// Evaluates an argument as *truthy*.
 ///
 /// For block execution if the value is *truthy* the inner template
@@ -16,6 +32,13 @@ use serde_json::Value;
 /// When executed in a statement this helper returns whether it's
 /// argument is *truthy*.
 ///
+/// Numeric zero is not *truthy* unless the `includeZero` hash
+/// parameter is *truthy*:
+///
+/// ```ignore
+/// {{#if count includeZero=true}}{{count}} items{{/if}}
+/// ```
+///
 pub struct If;
 
 impl Helper for If {
@@ -28,14 +51,14 @@ impl Helper for If {
         ctx.arity(1..1)?;
 
         if let Some(template) = template {
-            if ctx.is_truthy(ctx.get(0).unwrap()) {
+            if condition(ctx) {
                 rc.template(template)?;
-            } else if let Some(node) = rc.inverse(template)? {
-                rc.template(node)?;
+            } else {
+                rc.render_inverse(template)?;
             }
             Ok(None)
         } else {
-            Ok(Some(Value::Bool(ctx.is_truthy(ctx.get(0).unwrap()))))
+            Ok(Some(Value::Bool(condition(ctx))))
         }
     }
 }
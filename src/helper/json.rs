@@ -6,15 +6,51 @@ use crate::{
     render::{Context, Render},
 };
 
-use serde_json::{to_string, to_string_pretty, Value};
+use serde::Serialize;
+use serde_json::{
+    ser::PrettyFormatter, to_string, to_string_pretty, Map, Serializer, Value,
+};
+
+const PRETTY: &str = "pretty";
+const INDENT: &str = "indent";
+const SORT_KEYS: &str = "sort_keys";
 
 /// Convert to a JSON string.
 ///
 /// Accepts a single argument which is converted to a JSON string and returned.
 ///
-/// The optional hash parameter `pretty` when *truthy* will pretty print the value.
+/// The optional hash parameter `pretty` when *truthy* will pretty print the value
+/// and the `indent` hash parameter (a non-negative integer) pretty prints using
+/// the given number of spaces for indentation. When `sort_keys` is *truthy*
+/// object keys are sorted so that the output is deterministic.
+///
+/// Like any other statement the returned string is HTML escaped so
+/// use the triple-stash syntax to output raw JSON:
+///
+/// ```ignore
+/// {{{json data indent=2}}}
+/// ```
 pub struct Json;
 
+/// Copy a value sorting the keys of all objects.
+fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let mut sorted = Map::new();
+            for (key, value) in entries {
+                sorted.insert(key.to_string(), sort_keys(value));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(list) => {
+            Value::Array(list.iter().map(sort_keys).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
 impl Helper for Json {
     fn call<'render, 'call>(
         &self,
@@ -26,8 +62,39 @@ impl Helper for Json {
 
         let target = ctx.get(0).unwrap();
         let pretty =
-            ctx.is_truthy(ctx.param("pretty").unwrap_or(&Value::Bool(false)));
-        let value = if pretty {
+            ctx.is_truthy(ctx.param(PRETTY).unwrap_or(&Value::Bool(false)));
+        let indent = if let Some(indent) = ctx.param(INDENT) {
+            Some(indent.as_u64().ok_or_else(|| {
+                HelperError::ParameterTypeUnsigned(
+                    ctx.name().to_string(),
+                    INDENT.to_string(),
+                )
+            })? as usize)
+        } else {
+            None
+        };
+
+        let sorted;
+        let target = if ctx
+            .is_truthy(ctx.param(SORT_KEYS).unwrap_or(&Value::Bool(false)))
+        {
+            sorted = sort_keys(target);
+            &sorted
+        } else {
+            target
+        };
+
+        let value = if let Some(indent) = indent {
+            let indent = " ".repeat(indent);
+            let mut buffer = Vec::new();
+            let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+            let mut serializer =
+                Serializer::with_formatter(&mut buffer, formatter);
+            target
+                .serialize(&mut serializer)
+                .map_err(HelperError::from)?;
+            Value::String(String::from_utf8_lossy(&buffer).into_owned())
+        } else if pretty {
             Value::String(to_string_pretty(&target).map_err(HelperError::from)?)
         } else {
             Value::String(to_string(&target).map_err(HelperError::from)?)
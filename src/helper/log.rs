@@ -6,8 +6,15 @@ use crate::{
     render::{Context, Render},
 };
 
+use std::str::FromStr;
+
 use log::*;
 
+pub use log::Level;
+
+/// Type for functions that capture log helper messages.
+pub type LogSinkFn<'reg> = Box<dyn Fn(Level, &str) + Send + Sync + 'reg>;
+
 /// Print a log message.
 ///
 /// Multiple arguments are accepted and concatenated using a
@@ -24,16 +31,22 @@ use log::*;
 /// * warn
 /// * error
 ///
+/// The default level is `info`.
+///
+/// If a log sink has been assigned using
+/// [set_log_sink()](crate::Registry#method.set_log_sink) messages
+/// are sent to the sink rather than the `log` facade.
+///
 pub struct Log;
 
 impl Helper for Log {
     fn call<'render, 'call>(
         &self,
-        _rc: &mut Render<'render>,
+        rc: &mut Render<'render>,
         ctx: &Context<'call>,
         _template: Option<&'render Node<'render>>,
     ) -> HelperValue {
-        ctx.arity(1..usize::MAX)?;
+        ctx.arity_min(1)?;
 
         let message = ctx
             .arguments()
@@ -44,18 +57,16 @@ impl Helper for Log {
 
         let level = ctx
             .param("level")
-            .map(|v| v.as_str())
-            .unwrap_or(Some("info"))
-            .unwrap();
+            .and_then(|v| v.as_str())
+            .and_then(|v| Level::from_str(v).ok())
+            .unwrap_or(Level::Info);
 
         let lines = message.split("\n");
         for line in lines {
-            match level {
-                "error" => error!("{}", line),
-                "debug" => debug!("{}", line),
-                "warn" => warn!("{}", line),
-                "trace" => trace!("{}", line),
-                _ => info!("{}", line),
+            if let Some(sink) = rc.registry().log_sink() {
+                sink(level, line);
+            } else {
+                log!(level, "{}", line);
             }
         }
 
@@ -61,3 +61,36 @@ impl Helper for Not {
         Ok(Some(Value::Bool(!ctx.is_truthy(ctx.get(0).unwrap()))))
     }
 }
+
+/// Convert an argument to a boolean using the truthiness rules.
+#[derive(Clone)]
+pub struct Truthy;
+
+impl Helper for Truthy {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        Ok(Some(Value::Bool(ctx.is_truthy(ctx.get(0).unwrap()))))
+    }
+}
+
+/// Convert an argument to a boolean that is true when the
+/// argument is not truthy.
+#[derive(Clone)]
+pub struct Falsy;
+
+impl Helper for Falsy {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        Ok(Some(Value::Bool(!ctx.is_truthy(ctx.get(0).unwrap()))))
+    }
+}
@@ -1,15 +1,19 @@
-//! Helper to lookup a field of an array or object.
+//! Helpers to lookup fields of arrays and objects.
 use crate::{
     error::HelperError,
     helper::{Helper, HelperValue},
+    json,
     parser::ast::Node,
     render::{Context, Render, Type},
 };
 
+use serde_json::Value;
+
 /// Lookup a field of an array of object.
 ///
 /// Requires exactly two arguments; the first is the target
-/// value and the second is a string field name.
+/// value and the second is a string field name or a number
+/// for array indices; negative indices count back from the end.
 ///
 /// If the target field could not be found this helper will
 /// return an error.
@@ -25,9 +29,13 @@ impl Helper for Lookup {
         ctx.arity(2..2)?;
 
         let target = ctx.get(0).unwrap();
-        let field = ctx.try_get(1, &[Type::String]).unwrap().as_str().unwrap();
+        let field = match ctx.try_get(1, &[Type::String, Type::Number])? {
+            Value::String(ref field) => field.to_string(),
+            // Numbers are array indices
+            index => index.to_string(),
+        };
 
-        if let Some(result) = ctx.lookup(&target, field).cloned() {
+        if let Some(result) = ctx.lookup(&target, &field).cloned() {
             Ok(Some(result))
         } else {
             Err(HelperError::LookupField(
@@ -37,3 +45,34 @@ impl Helper for Lookup {
         }
     }
 }
+
+/// Determine if a path exists in an array or object.
+///
+/// Requires exactly two arguments; the first is the target value
+/// and the second is a string path using dot delimiters, for
+/// example `"a.b.c"`; array indices are path components.
+///
+/// Returns `true` when every component of the path is present
+/// even if the final value is `null` and `false` when traversal
+/// stops at a missing component:
+///
+/// ```ignore
+/// {{#if (has author "address.city")}}{{author.address.city}}{{/if}}
+/// ```
+pub struct Has;
+
+impl Helper for Has {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+
+        let target = ctx.get(0).unwrap();
+        let path = ctx.try_get(1, &[Type::String])?.as_str().unwrap();
+        let found = json::find_parts(path.split('.'), target).is_some();
+        Ok(Some(Value::Bool(found)))
+    }
+}
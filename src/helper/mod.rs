@@ -18,19 +18,23 @@
 //! When a helper is called as a block the optional template node will be `Some`.
 //! Raw helpers can access the inner text using [text()](crate::render::context::Context#method.text).
 //!
-//! To determine how a helper was invoked requires checking for an inner template
-//! or raw text; if neither is available it is a statement:
+//! To determine how a helper was invoked use [invocation()](crate::render::context::Context#method.invocation):
 //!
 //! ```ignore
-//! if let Some(node) = template {
+//! match ctx.invocation() {
+//!     // Helper was invoked as a statement `{{helper}}`
+//!     Invocation::Statement => {}
 //!     // Helper was invoked as a block `{{#helper}}...{{/helper}}`
-//! } else if let Some(text) = ctx.text() {
+//!     Invocation::Block => {}
 //!     // Helper was invoked as a raw block `{{{{helper}}}}...{{{{/helper}}}}`
-//! } else {
-//!     // Helper was invoked as a statement `{{helper}}`
+//!     Invocation::Raw => {}
 //! }
 //! ```
 //!
+//! The [is_block()](crate::render::context::Context#method.is_block) and
+//! [is_raw()](crate::render::context::Context#method.is_raw) predicates are
+//! also available; note that raw blocks are also blocks.
+//!
 //! ## Type Assertions
 //!
 //! Type assertions let us verify the type of helper arguments and hash parameters before we
@@ -83,6 +87,28 @@
 //! }
 //! ```
 //!
+//! ## Else Branches
+//!
+//! When a block helper is called with an `else` clause (or chained
+//! `else if` clauses) the branches are conditions of the block node.
+//! Use [render_inverse()](crate::render::Render#method.render_inverse)
+//! to render the first matching branch when the helper does not render
+//! the inner template; it returns `false` when there is no branch to
+//! render. This is how the `if` and `unless` helpers are implemented:
+//!
+//! ```ignore
+//! if let Some(node) = template {
+//!     if condition {
+//!         rc.template(node)?;
+//!     } else {
+//!         rc.render_inverse(node)?;
+//!     }
+//! }
+//! ```
+//!
+//! To select the branch without rendering it use
+//! [inverse()](crate::render::Render#method.inverse).
+//!
 //! ## Raw Paths
 //!
 //! Most of the time helpers operate on the [Value](serde_json::Value) type but sometimes it is
@@ -139,6 +165,9 @@ use crate::{
     render::{Context, Render},
 };
 
+#[cfg(feature = "types-helper")]
+use crate::render::Type;
+
 /// Result type returned when invoking helpers.
 pub type HelperResult<T> = std::result::Result<T, HelperError>;
 
@@ -195,10 +224,14 @@ dyn_clone::clone_trait_object!(LocalHelper);
 
 pub mod prelude;
 
+#[cfg(feature = "array-helper")]
+pub mod array;
 #[cfg(feature = "comparison-helper")]
 pub mod comparison;
 #[cfg(feature = "each-helper")]
 pub mod each;
+#[cfg(feature = "env-helper")]
+pub mod env;
 #[cfg(feature = "conditional-helper")]
 pub mod r#if;
 #[cfg(feature = "json-helper")]
@@ -209,11 +242,65 @@ pub mod log;
 pub mod logical;
 #[cfg(feature = "lookup-helper")]
 pub mod lookup;
+#[cfg(feature = "number-helper")]
+pub mod number;
+#[cfg(feature = "string-helper")]
+pub mod string;
+#[cfg(feature = "types-helper")]
+pub mod types;
 #[cfg(feature = "conditional-helper")]
 pub mod unless;
+#[cfg(feature = "url-helper")]
+pub mod url;
 #[cfg(feature = "with-helper")]
 pub mod with;
 
+/// Names of the builtin helpers.
+const BUILTINS: &[&str] = &[
+    "if",
+    "unless",
+    "eq",
+    "ne",
+    "gt",
+    "gte",
+    "lt",
+    "lte",
+    "log",
+    "lookup",
+    "has",
+    "and",
+    "or",
+    "not",
+    "truthy",
+    "falsy",
+    "with",
+    "each",
+    "json",
+    "repeat",
+    "len",
+    "indent",
+    "titlecase",
+    "classnames",
+    "trim",
+    "trim_start",
+    "trim_end",
+    "first",
+    "last",
+    "range",
+    "chunk",
+    "is_array",
+    "is_object",
+    "is_string",
+    "is_number",
+    "is_bool",
+    "is_null",
+    "number",
+    "percent",
+    "filesize",
+    "urlencode",
+    "urldecode",
+];
+
 /// Collection of helpers.
 #[derive(Default)]
 pub struct HelperRegistry<'reg> {
@@ -237,61 +324,176 @@ impl<'reg> HelperRegistry<'reg> {
     }
 
     fn builtins(&mut self) {
-        #[cfg(feature = "conditional-helper")]
-        self.insert("if", Box::new(r#if::If {}));
-        #[cfg(feature = "conditional-helper")]
-        self.insert("unless", Box::new(unless::Unless {}));
-
-        #[cfg(feature = "comparison-helper")]
-        self.insert("eq", Box::new(comparison::Equal {}));
-        #[cfg(feature = "comparison-helper")]
-        self.insert("ne", Box::new(comparison::NotEqual {}));
-        #[cfg(feature = "comparison-helper")]
-        self.insert("gt", Box::new(comparison::GreaterThan {}));
-        #[cfg(feature = "comparison-helper")]
-        self.insert("gte", Box::new(comparison::GreaterThanEqual {}));
-        #[cfg(feature = "comparison-helper")]
-        self.insert("lt", Box::new(comparison::LessThan {}));
-        #[cfg(feature = "comparison-helper")]
-        self.insert("lte", Box::new(comparison::LessThanEqual {}));
-
-        #[cfg(feature = "log-helper")]
-        self.insert("log", Box::new(log::Log {}));
-        #[cfg(feature = "lookup-helper")]
-        self.insert("lookup", Box::new(lookup::Lookup {}));
-
-        #[cfg(feature = "logical-helper")]
-        self.insert("and", Box::new(logical::And {}));
-        #[cfg(feature = "logical-helper")]
-        self.insert("or", Box::new(logical::Or {}));
-        #[cfg(feature = "logical-helper")]
-        self.insert("not", Box::new(logical::Not {}));
+        for name in BUILTINS {
+            if let Some(helper) = Self::builtin(name) {
+                self.insert(name, helper);
+            }
+        }
+    }
 
-        #[cfg(feature = "with-helper")]
-        self.insert("with", Box::new(with::With {}));
-        #[cfg(feature = "each-helper")]
-        self.insert("each", Box::new(each::Each {}));
+    /// Create a builtin helper when it is enabled by the feature flags.
+    fn builtin(name: &str) -> Option<Box<dyn Helper + 'reg>> {
+        match name {
+            #[cfg(feature = "conditional-helper")]
+            "if" => Some(Box::new(r#if::If {})),
+            #[cfg(feature = "conditional-helper")]
+            "unless" => Some(Box::new(unless::Unless {})),
+            #[cfg(feature = "comparison-helper")]
+            "eq" => Some(Box::new(comparison::Equal {})),
+            #[cfg(feature = "comparison-helper")]
+            "ne" => Some(Box::new(comparison::NotEqual {})),
+            #[cfg(feature = "comparison-helper")]
+            "gt" => Some(Box::new(comparison::GreaterThan {})),
+            #[cfg(feature = "comparison-helper")]
+            "gte" => Some(Box::new(comparison::GreaterThanEqual {})),
+            #[cfg(feature = "comparison-helper")]
+            "lt" => Some(Box::new(comparison::LessThan {})),
+            #[cfg(feature = "comparison-helper")]
+            "lte" => Some(Box::new(comparison::LessThanEqual {})),
+            #[cfg(feature = "log-helper")]
+            "log" => Some(Box::new(log::Log {})),
+            #[cfg(feature = "lookup-helper")]
+            "lookup" => Some(Box::new(lookup::Lookup {})),
+            #[cfg(feature = "lookup-helper")]
+            "has" => Some(Box::new(lookup::Has {})),
+            #[cfg(feature = "logical-helper")]
+            "and" => Some(Box::new(logical::And {})),
+            #[cfg(feature = "logical-helper")]
+            "or" => Some(Box::new(logical::Or {})),
+            #[cfg(feature = "logical-helper")]
+            "not" => Some(Box::new(logical::Not {})),
+            #[cfg(feature = "logical-helper")]
+            "truthy" => Some(Box::new(logical::Truthy {})),
+            #[cfg(feature = "logical-helper")]
+            "falsy" => Some(Box::new(logical::Falsy {})),
+            #[cfg(feature = "with-helper")]
+            "with" => Some(Box::new(with::With {})),
+            #[cfg(feature = "each-helper")]
+            "each" => Some(Box::new(each::Each {})),
+            #[cfg(feature = "json-helper")]
+            "json" => Some(Box::new(json::Json {})),
+            #[cfg(feature = "string-helper")]
+            "repeat" => Some(Box::new(string::Repeat {})),
+            #[cfg(feature = "string-helper")]
+            "len" => Some(Box::new(string::Len {})),
+            #[cfg(feature = "string-helper")]
+            "indent" => Some(Box::new(string::Indent {})),
+            #[cfg(feature = "string-helper")]
+            "titlecase" => Some(Box::new(string::TitleCase {})),
+            #[cfg(feature = "string-helper")]
+            "classnames" => Some(Box::new(string::ClassNames {})),
+            #[cfg(feature = "string-helper")]
+            "trim" => Some(Box::new(string::Trim(string::TrimSide::Both))),
+            #[cfg(feature = "string-helper")]
+            "trim_start" => {
+                Some(Box::new(string::Trim(string::TrimSide::Start)))
+            }
+            #[cfg(feature = "string-helper")]
+            "trim_end" => Some(Box::new(string::Trim(string::TrimSide::End))),
+            #[cfg(feature = "array-helper")]
+            "first" => Some(Box::new(array::First {})),
+            #[cfg(feature = "array-helper")]
+            "last" => Some(Box::new(array::Last {})),
+            #[cfg(feature = "array-helper")]
+            "range" => Some(Box::new(array::Range {})),
+            #[cfg(feature = "array-helper")]
+            "chunk" => Some(Box::new(array::Chunk {})),
+            #[cfg(feature = "types-helper")]
+            "is_array" => Some(Box::new(types::IsType(Type::Array))),
+            #[cfg(feature = "types-helper")]
+            "is_object" => Some(Box::new(types::IsType(Type::Object))),
+            #[cfg(feature = "types-helper")]
+            "is_string" => Some(Box::new(types::IsType(Type::String))),
+            #[cfg(feature = "types-helper")]
+            "is_number" => Some(Box::new(types::IsType(Type::Number))),
+            #[cfg(feature = "types-helper")]
+            "is_bool" => Some(Box::new(types::IsType(Type::Bool))),
+            #[cfg(feature = "types-helper")]
+            "is_null" => Some(Box::new(types::IsType(Type::Null))),
+            #[cfg(feature = "number-helper")]
+            "number" => Some(Box::new(number::Number {})),
+            #[cfg(feature = "number-helper")]
+            "percent" => Some(Box::new(number::Percent {})),
+            #[cfg(feature = "number-helper")]
+            "filesize" => Some(Box::new(number::FileSize {})),
+            #[cfg(feature = "url-helper")]
+            "urlencode" => Some(Box::new(url::UrlEncode {})),
+            #[cfg(feature = "url-helper")]
+            "urldecode" => Some(Box::new(url::UrlDecode {})),
+            _ => None,
+        }
+    }
 
-        #[cfg(feature = "json-helper")]
-        self.insert("json", Box::new(json::Json {}));
+    /// Insert the builtin helper with the given name.
+    ///
+    /// Use this to restore a builtin helper that was removed or
+    /// replaced; returns `false` when there is no builtin helper with
+    /// the given name or the helper was disabled by the feature flags.
+    pub fn enable_builtin(&mut self, name: &str) -> bool {
+        match BUILTINS.iter().find(|n| **n == name) {
+            Some(name) => match Self::builtin(name) {
+                Some(helper) => {
+                    self.insert(name, helper);
+                    true
+                }
+                None => false,
+            },
+            None => false,
+        }
     }
 
     /// Insert a helper into this collection.
-    pub fn insert(&mut self, name: &'reg str, helper: Box<dyn Helper + 'reg>) {
+    ///
+    /// If a helper already exists with the same name it is replaced
+    /// and the previous helper is returned so that callers can detect
+    /// when a helper (including a builtin) has been shadowed.
+    pub fn insert(
+        &mut self,
+        name: &'reg str,
+        helper: Box<dyn Helper + 'reg>,
+    ) -> Option<Box<dyn Helper + 'reg>> {
+        self.helpers.insert(name, helper)
+    }
+
+    /// Insert a helper into this collection discarding any previous helper.
+    #[deprecated(since = "0.12.0", note = "Use insert() instead.")]
+    pub fn insert_unchecked(
+        &mut self,
+        name: &'reg str,
+        helper: Box<dyn Helper + 'reg>,
+    ) {
         self.helpers.insert(name, helper);
     }
 
     /// Remove a helper from this collection.
-    pub fn remove(&mut self, name: &'reg str) {
-        self.helpers.remove(name);
+    ///
+    /// The removed helper is returned if it exists.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Helper + 'reg>> {
+        self.helpers.remove(name)
     }
 
     /// Get a helper from this collection.
     pub fn get(&self, name: &str) -> Option<&Box<dyn Helper + 'reg>> {
         self.helpers.get(name)
     }
+
+    /// Determine if a helper exists in this collection.
+    pub fn contains(&self, name: &str) -> bool {
+        self.helpers.contains_key(name)
+    }
+
+    /// Iterate the names of the helpers in this collection.
+    ///
+    /// Iteration order is not guaranteed.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.helpers.keys().copied()
+    }
 }
 
+/// Type for functions that resolve link hrefs.
+pub type LinkResolverFn<'reg> =
+    Box<dyn Fn(&str) -> String + Send + Sync + 'reg>;
+
 /// Collection of helpers that are not for general purpose use.
 ///
 /// That is they cannot be invoked directly from a template but are
@@ -300,6 +502,9 @@ impl<'reg> HelperRegistry<'reg> {
 pub struct HandlerRegistry<'reg> {
     /// Helper invoked when a link node is encountered by the renderer.
     pub link: Option<Box<dyn Helper + 'reg>>,
+    /// Function invoked to rewrite a link href before it is
+    /// passed to the link helper.
+    pub link_resolver: Option<LinkResolverFn<'reg>>,
     /// Helper invoked when a helper is missing.
     pub helper_missing: Option<Box<dyn Helper + 'reg>>,
     /// Helper invoked when a block helper is missing.
@@ -0,0 +1,218 @@
+//! Helpers for formatting numbers.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+use serde_json::Value;
+
+const PRECISION: &str = "precision";
+const SEPARATOR: &str = "separator";
+const BINARY: &str = "binary";
+
+/// Maximum number of decimal places allowed by the `precision`
+/// hash parameter.
+pub const MAX_PRECISION: u64 = 100;
+
+const DECIMAL_UNITS: [&str; 7] = ["B", "KB", "MB", "GB", "TB", "PB", "EB"];
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Format a number.
+///
+/// Accepts a single numeric argument and returns a string.
+///
+/// The `precision` hash parameter sets the number of decimal places
+/// up to [MAX_PRECISION](crate::helper::number::MAX_PRECISION);
+/// when it is not given the number is written unchanged. The
+/// `separator` hash parameter is inserted between groups of
+/// thousands in the integer part:
+///
+/// ```ignore
+/// {{number 1234.5 precision=2 separator=","}}
+/// ```
+pub struct Number;
+
+impl Helper for Number {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::Number])?;
+        let formatted = match precision(ctx)? {
+            Some(precision) => {
+                format!("{:.*}", precision, value.as_f64().unwrap())
+            }
+            None => value.to_string(),
+        };
+        Ok(Some(Value::String(group(&formatted, separator(ctx)?))))
+    }
+}
+
+/// Format a ratio as a percentage.
+///
+/// Accepts a single numeric argument which is multiplied by 100
+/// and returns a string with a `%` suffix:
+///
+/// ```ignore
+/// {{percent 0.25}}
+/// ```
+///
+/// Supports the same `precision` and `separator` hash parameters
+/// as the [number](crate::helper::number::Number) helper; without
+/// a `precision` the result is rounded to 15 significant digits to
+/// remove floating point error from the multiplication.
+///
+/// When the result is not finite the representation configured with
+/// [set_non_finite_repr()](crate::Registry#method.set_non_finite_repr)
+/// is returned without the suffix.
+pub struct Percent;
+
+impl Helper for Percent {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::Number])?.as_f64().unwrap() * 100.0;
+        if !value.is_finite() {
+            return Ok(Some(rc.float(value)));
+        }
+        let formatted = match precision(ctx)? {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => {
+                // Round so that 0.07 is 7% rather than 7.000000000000001%
+                let value: f64 =
+                    format!("{:.14e}", value).parse().unwrap_or(value);
+                value.to_string()
+            }
+        };
+        Ok(Some(Value::String(format!(
+            "{}%",
+            group(&formatted, separator(ctx)?)
+        ))))
+    }
+}
+
+/// Format a byte count as a file size.
+///
+/// Accepts a single non-negative number argument and returns a
+/// string using decimal units (`KB`, `MB` etc.) that are powers of
+/// 1000:
+///
+/// ```ignore
+/// {{filesize 1200000}}
+/// ```
+///
+/// Renders `1.2 MB`. When the `binary` hash parameter is *truthy*
+/// binary units (`KiB`, `MiB` etc.) that are powers of 1024 are used.
+/// The `precision` hash parameter sets the number of decimal places
+/// and defaults to one; byte counts less than one unit are rounded
+/// and written without decimal places.
+pub struct FileSize;
+
+impl Helper for FileSize {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let bytes = ctx
+            .get(0)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite() && *v >= 0.0)
+            .ok_or_else(|| {
+                HelperError::ArgumentTypeNonNegative(ctx.name().to_string(), 0)
+            })?;
+        let binary =
+            ctx.is_truthy(ctx.param(BINARY).unwrap_or(&Value::Bool(false)));
+        let precision = precision(ctx)?.unwrap_or(1);
+
+        let (base, units) = if binary {
+            (1024.0, &BINARY_UNITS)
+        } else {
+            (1000.0, &DECIMAL_UNITS)
+        };
+
+        let mut size = bytes;
+        let mut unit = 0;
+        while size >= base && unit < units.len() - 1 {
+            size /= base;
+            unit += 1;
+        }
+
+        // Rounding may carry the size over to the next unit
+        let places = if unit == 0 { 0 } else { precision };
+        if unit < units.len() - 1 {
+            let rounded: f64 =
+                format!("{:.*}", places, size).parse().unwrap_or(size);
+            if rounded >= base {
+                size /= base;
+                unit += 1;
+            }
+        }
+
+        let places = if unit == 0 { 0 } else { precision };
+        let formatted = format!("{:.*} {}", places, size, units[unit]);
+        Ok(Some(Value::String(formatted)))
+    }
+}
+
+fn precision(ctx: &Context<'_>) -> Result<Option<usize>, HelperError> {
+    if let Some(precision) = ctx.param(PRECISION) {
+        let precision = precision.as_u64().ok_or_else(|| {
+            HelperError::ParameterTypeUnsigned(
+                ctx.name().to_string(),
+                PRECISION.to_string(),
+            )
+        })?;
+        if precision > MAX_PRECISION {
+            return Err(HelperError::ParameterLimit(
+                ctx.name().to_string(),
+                PRECISION.to_string(),
+                MAX_PRECISION,
+            ));
+        }
+        Ok(Some(precision as usize))
+    } else {
+        Ok(None)
+    }
+}
+
+fn separator<'a>(ctx: &'a Context<'_>) -> Result<&'a str, HelperError> {
+    Ok(ctx
+        .try_param(SEPARATOR, &[Type::String, Type::Null])?
+        .as_str()
+        .unwrap_or(""))
+}
+
+/// Insert a separator between groups of thousands in the
+/// integer part of a formatted number.
+fn group(formatted: &str, separator: &str) -> String {
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", formatted),
+    };
+    let end = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    let (digits, rest) = unsigned.split_at(end);
+
+    let mut result = String::from(sign);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            result.push_str(separator);
+        }
+        result.push(digit);
+    }
+    result.push_str(rest);
+    result
+}
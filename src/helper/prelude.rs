@@ -2,7 +2,8 @@
 pub use crate::{
     error::HelperError,
     helper::{Helper, HelperResult, HelperValue, LocalHelper},
+    helper_error,
     parser::ast::Node,
-    render::{Context, Render, Scope, Type},
+    render::{Context, Invocation, Render, Scope, Type},
     template::Template,
 };
@@ -0,0 +1,366 @@
+//! Helpers for strings.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+
+use serde_json::{Number, Value};
+use unicode_segmentation::UnicodeSegmentation;
+
+const INDEX: &str = "index";
+const BLANKS: &str = "blanks";
+const SMALL_WORDS: &str = "small_words";
+
+/// Maximum number of repetitions allowed by the `repeat` helper.
+pub const MAX_REPEAT: u64 = 10_000;
+
+/// Maximum length in bytes of a string returned by the `repeat` helper.
+pub const MAX_REPEAT_LENGTH: u64 = 1_000_000;
+
+/// Repeat a string or an inner template.
+///
+/// As a statement accepts a string and a count and returns the
+/// string repeated count times:
+///
+/// ```ignore
+/// {{repeat "  " 4}}
+/// ```
+///
+/// As a block accepts a single count argument and renders the
+/// inner template count times setting the `@index` local variable
+/// to the current zero-based index:
+///
+/// ```ignore
+/// {{#repeat 3}}{{@index}}{{/repeat}}
+/// ```
+///
+/// The count must be a non-negative integer no greater
+/// than [MAX_REPEAT](crate::helper::string::MAX_REPEAT) and a
+/// repeated string may be no longer than
+/// [MAX_REPEAT_LENGTH](crate::helper::string::MAX_REPEAT_LENGTH) bytes.
+pub struct Repeat;
+
+impl Repeat {
+    fn count(
+        &self,
+        ctx: &Context<'_>,
+        index: usize,
+    ) -> Result<usize, HelperError> {
+        let count =
+            ctx.get(index).and_then(|v| v.as_u64()).ok_or_else(|| {
+                HelperError::ArgumentTypeUnsigned(ctx.name().to_string(), index)
+            })?;
+        if count > MAX_REPEAT {
+            return Err(HelperError::ArgumentLimit(
+                ctx.name().to_string(),
+                index,
+                MAX_REPEAT,
+            ));
+        }
+        Ok(count as usize)
+    }
+}
+
+impl Helper for Repeat {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        if let Some(template) = template {
+            ctx.arity(1..1)?;
+            let count = self.count(ctx, 0)?;
+            rc.assert_iterations(ctx.name(), count)?;
+
+            let mut scope = Scope::new();
+            if let Some(base) =
+                rc.scope_mut().and_then(|s| s.base_value().clone())
+            {
+                scope.set_base_value(base);
+            }
+
+            rc.push_scope(scope);
+            for index in 0..count {
+                if let Some(ref mut scope) = rc.scope_mut() {
+                    scope.set_local(INDEX, Value::Number(Number::from(index)));
+                }
+                rc.template(template)?;
+            }
+            rc.pop_scope();
+            Ok(None)
+        } else {
+            ctx.arity(2..2)?;
+            let value =
+                ctx.get(0).and_then(|v| v.as_str()).ok_or_else(|| {
+                    HelperError::ArgumentTypeString(ctx.name().to_string(), 0)
+                })?;
+            let count = self.count(ctx, 1)?;
+            rc.assert_iterations(ctx.name(), count)?;
+            if value.len() as u64 * count as u64 > MAX_REPEAT_LENGTH {
+                return Err(HelperError::RepeatLimit(
+                    ctx.name().to_string(),
+                    MAX_REPEAT_LENGTH,
+                ));
+            }
+            Ok(Some(Value::String(value.repeat(count))))
+        }
+    }
+}
+
+/// Get the length of a string, array or object.
+///
+/// Accepts a single argument and returns the number of elements
+/// in an array, the number of keys in an object or the number of
+/// grapheme clusters (user-perceived characters) in a string:
+///
+/// ```ignore
+/// {{len title}}
+/// ```
+///
+/// Other types are an error.
+pub struct Len;
+
+impl Helper for Len {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value =
+            ctx.try_get(0, &[Type::String, Type::Array, Type::Object])?;
+        let length = match value {
+            Value::String(ref s) => s.graphemes(true).count(),
+            Value::Array(ref list) => list.len(),
+            Value::Object(ref map) => map.len(),
+            _ => unreachable!(),
+        };
+        Ok(Some(Value::from(length)))
+    }
+}
+
+/// Indent every line of an inner template.
+///
+/// Accepts a single string argument which is prepended to each
+/// line of the rendered inner template:
+///
+/// ```ignore
+/// {{#indent "  "}}
+/// name: {{name}}
+/// {{/indent}}
+/// ```
+///
+/// Empty lines are not indented unless the `blanks` hash parameter
+/// is truthy; a trailing newline is preserved and does not start a
+/// new line to indent.
+pub struct Indent;
+
+impl Helper for Indent {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let prefix = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let blanks =
+            ctx.is_truthy(ctx.param(BLANKS).unwrap_or(&Value::Bool(false)));
+        let content = rc.buffer(ctx.assert_block(template)?)?;
+
+        let mut result = String::with_capacity(content.len());
+        for line in content.split_inclusive('\n') {
+            if blanks || !line.trim_end_matches(&['\r', '\n'][..]).is_empty() {
+                result.push_str(prefix);
+            }
+            result.push_str(line);
+        }
+        rc.write(&result)?;
+        Ok(None)
+    }
+}
+
+/// Convert a string to title case.
+///
+/// Accepts a single string argument and returns a string where the
+/// first character of each word is converted to upper case and the
+/// remaining characters are converted to lower case; words are
+/// separated by whitespace which is preserved. When the upper case
+/// form of the first character has more than one character only the
+/// first of them is upper case, so `ß` becomes `Ss`:
+///
+/// ```ignore
+/// {{titlecase "hello world"}}
+/// ```
+///
+/// The `small_words` hash parameter lists words that are written in
+/// lower case unless they are the first word; it may be an array of
+/// strings or a string of whitespace-separated words:
+///
+/// ```ignore
+/// {{titlecase "the lord of the rings" small_words="a the of"}}
+/// ```
+pub struct TitleCase;
+
+impl TitleCase {
+    fn small_words(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<String>, HelperError> {
+        let invalid = || {
+            HelperError::ParameterTypeStringArray(
+                ctx.name().to_string(),
+                SMALL_WORDS.to_string(),
+            )
+        };
+        match ctx.param(SMALL_WORDS) {
+            Some(Value::Array(ref words)) => words
+                .iter()
+                .map(|w| {
+                    w.as_str().map(|w| w.to_lowercase()).ok_or_else(invalid)
+                })
+                .collect(),
+            Some(Value::String(ref words)) => {
+                Ok(words.split_whitespace().map(|w| w.to_lowercase()).collect())
+            }
+            Some(Value::Null) | None => Ok(Vec::new()),
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+impl Helper for TitleCase {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let small_words = self.small_words(ctx)?;
+
+        let mut result = String::with_capacity(value.len());
+        let mut first = true;
+        let mut rest = value;
+        while !rest.is_empty() {
+            let start = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let word = rest[..end].to_lowercase();
+            rest = &rest[end..];
+            if word.is_empty() {
+                continue;
+            }
+            if !first && small_words.contains(&word) {
+                result.push_str(&word);
+            } else {
+                let mut chars = word.chars();
+                if let Some(c) = chars.next() {
+                    // Only the first character of a multiple character
+                    // upper case form stays upper case, eg: `ß` is `Ss`
+                    let mut upper = c.to_uppercase();
+                    result.extend(upper.next());
+                    for c in upper {
+                        result.extend(c.to_lowercase());
+                    }
+                }
+                result.push_str(chars.as_str());
+            }
+            first = false;
+        }
+        Ok(Some(Value::String(result)))
+    }
+}
+
+/// Compose a list of class names.
+///
+/// Every positional argument must be a string and is always included;
+/// each hash parameter name is included when the parameter value is
+/// *truthy*. Names are joined with a single space and empty names are
+/// skipped; positional arguments always come first followed by the hash
+/// parameters in template order:
+///
+/// ```ignore
+/// {{classnames "btn" primary=true muted=false}}
+/// ```
+///
+/// Renders `btn primary`.
+pub struct ClassNames;
+
+impl Helper for ClassNames {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..usize::MAX)?;
+        let mut names: Vec<&str> = ctx
+            .try_get_all(&[Type::String])?
+            .iter()
+            .map(|name| name.as_str().unwrap())
+            .collect();
+        for name in ctx.hash_keys() {
+            if let Some(value) = ctx.param(name) {
+                if ctx.is_truthy(value) {
+                    names.push(name);
+                }
+            }
+        }
+        names.retain(|name| !name.is_empty());
+        Ok(Some(Value::String(names.join(" "))))
+    }
+}
+
+/// Whitespace removed by the [Trim](Trim) helper.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TrimSide {
+    /// Remove leading and trailing whitespace.
+    Both,
+    /// Remove leading whitespace.
+    Start,
+    /// Remove trailing whitespace.
+    End,
+}
+
+/// Trim whitespace from the rendered inner template.
+///
+/// Registered as `trim`, `trim_start` and `trim_end` which remove
+/// leading and trailing, leading or trailing whitespace respectively:
+///
+/// ```ignore
+/// {{#trim}} {{title}} {{/trim}}
+/// ```
+///
+/// Unlike the `~` whitespace control markers which only affect
+/// adjacent text in the template the whitespace is removed from
+/// the fully rendered content so it also applies to whitespace in
+/// variables and the output of helpers and partials.
+pub struct Trim(pub TrimSide);
+
+impl Helper for Trim {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+        let content = rc.buffer(ctx.assert_block(template)?)?;
+        let result = match self.0 {
+            TrimSide::Both => content.trim(),
+            TrimSide::Start => content.trim_start(),
+            TrimSide::End => content.trim_end(),
+        };
+        rc.write(result)?;
+        Ok(None)
+    }
+}
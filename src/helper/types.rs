@@ -0,0 +1,35 @@
+//! Helpers for type predicates.
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+use serde_json::Value;
+
+/// Determine if an argument is of a type.
+///
+/// Accepts a single argument and returns a boolean indicating
+/// whether the argument is of the given type; registered as
+/// `is_array`, `is_object`, `is_string`, `is_number`, `is_bool`
+/// and `is_null`:
+///
+/// ```ignore
+/// {{#if (is_array items)}}{{#each items}}{{this}}{{/each}}{{else}}{{items}}{{/if}}
+/// ```
+///
+/// Missing values are `null`.
+pub struct IsType(pub Type);
+
+impl Helper for IsType {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.get(0).unwrap();
+        Ok(Some(Value::Bool(Type::from(value) == self.0)))
+    }
+}
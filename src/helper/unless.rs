@@ -1,6 +1,6 @@
 //! Block helper for negated conditional.
 use crate::{
-    helper::{Helper, HelperValue},
+    helper::{r#if::condition, Helper, HelperValue},
     parser::ast::Node,
     render::{Context, Render},
 };
@@ -9,6 +9,8 @@ use crate::{
 ///
 /// If any `else` or `else if` conditionals are present they will
 /// be rendered when necessary.
+///
+/// Supports the `includeZero` hash parameter in the same way as `if`.
 pub struct Unless;
 
 impl Helper for Unless {
@@ -21,10 +23,10 @@ impl Helper for Unless {
         ctx.arity(1..1)?;
 
         if let Some(template) = template {
-            if !ctx.is_truthy(ctx.get(0).unwrap()) {
+            if !condition(ctx) {
                 rc.template(template)?;
-            } else if let Some(node) = rc.inverse(template)? {
-                rc.template(node)?;
+            } else {
+                rc.render_inverse(template)?;
             }
         }
 
@@ -0,0 +1,109 @@
+//! Helpers for encoding and decoding URL components.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+use serde_json::Value;
+
+const PLUS: &str = "plus";
+
+fn plus(ctx: &Context<'_>) -> bool {
+    ctx.is_truthy(ctx.param(PLUS).unwrap_or(&Value::Bool(false)))
+}
+
+/// Percent-encode a string.
+///
+/// Accepts a single string argument and encodes it using the
+/// `application/x-www-form-urlencoded` rules; ASCII alphanumeric
+/// characters and `*-._` are not encoded and spaces are encoded
+/// as `%20` unless the `plus` hash parameter is truthy:
+///
+/// ```ignore
+/// <a href="/search?q={{urlencode query plus=true}}">
+/// ```
+pub struct UrlEncode;
+
+impl Helper for UrlEncode {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let plus = plus(ctx);
+        let mut result = String::with_capacity(value.len());
+        for byte in value.bytes() {
+            match byte {
+                b'A'..=b'Z'
+                | b'a'..=b'z'
+                | b'0'..=b'9'
+                | b'*'
+                | b'-'
+                | b'.'
+                | b'_' => result.push(byte as char),
+                b' ' if plus => result.push('+'),
+                _ => result.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        Ok(Some(Value::String(result)))
+    }
+}
+
+/// Decode a percent-encoded string.
+///
+/// Accepts a single string argument and decodes percent-encoded
+/// bytes; when the `plus` hash parameter is truthy `+` is decoded
+/// as a space. Percent signs that are not followed by two hexadecimal
+/// digits are not modified; it is an error if the decoded bytes
+/// are not valid UTF-8.
+pub struct UrlDecode;
+
+impl Helper for UrlDecode {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let plus = plus(ctx);
+        let bytes = value.as_bytes();
+        let mut result: Vec<u8> = Vec::with_capacity(bytes.len());
+        let mut index = 0;
+        while index < bytes.len() {
+            let byte = bytes[index];
+            match byte {
+                b'%' => {
+                    let decoded = value
+                        .get(index + 1..index + 3)
+                        .filter(|hex| {
+                            hex.bytes().all(|b| b.is_ascii_hexdigit())
+                        })
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                    if let Some(decoded) = decoded {
+                        result.push(decoded);
+                        index += 3;
+                        continue;
+                    }
+                    result.push(byte);
+                }
+                b'+' if plus => result.push(b' '),
+                _ => result.push(byte),
+            }
+            index += 1;
+        }
+        let result = String::from_utf8(result).map_err(|_| {
+            HelperError::new(format!(
+                "Helper '{}' decoded invalid UTF-8",
+                ctx.name()
+            ))
+        })?;
+        Ok(Some(Value::String(result)))
+    }
+}
@@ -8,6 +8,19 @@ use crate::{
 use serde_json::Value;
 
 /// Set the scope for a block to the target argument.
+///
+/// When the target is null or missing the inner template
+/// is not rendered and the `else` branch is rendered instead:
+///
+/// ```ignore
+/// {{#with user}}{{name}}{{else}}No user{{/with}}
+/// ```
+///
+/// A block parameter may be used to name the target:
+///
+/// ```ignore
+/// {{#with user as |author|}}{{author.name}}{{/with}}
+/// ```
 pub struct With;
 
 impl Helper for With {
@@ -19,14 +32,19 @@ impl Helper for With {
     ) -> HelperValue {
         ctx.arity(1..1)?;
 
-        if let Some(arg) = ctx.get(0) {
-            let is_null = if let Value::Null = arg { true } else { false };
-            if !is_null {
-                if let Some(template) = template {
-                    rc.push_scope(Scope::new());
-                    if let Some(ref mut scope) = rc.scope_mut() {
-                        scope.set_base_value(ctx.get(0).cloned().unwrap());
+        if let Some(template) = template {
+            // Missing values are coerced to null
+            match ctx.get(0) {
+                Some(Value::Null) | None => {
+                    rc.render_inverse(template)?;
+                }
+                Some(arg) => {
+                    let mut scope = Scope::new();
+                    scope.set_base_value(arg.clone());
+                    if let Some(name) = ctx.call().block_params().first() {
+                        scope.set_block_param(name, arg.clone());
                     }
+                    rc.push_scope(scope);
                     rc.template(template)?;
                     rc.pop_scope();
                 }
@@ -1,6 +1,8 @@
 //! Helper functions for working with JSON values.
 use serde_json::Value;
 
+use crate::registry::NonFinite;
+
 const OBJECT: &str = "Object";
 const ARRAY: &str = "Array";
 
@@ -13,6 +15,25 @@ pub(crate) fn stringify(value: &Value) -> String {
     }
 }
 
+// Values never contain non-finite numbers as serde_json converts
+// them to null so they are handled when a float is converted to
+// a value, see `Render::float()`.
+pub(crate) fn stringify_non_finite(
+    value: f64,
+    repr: &NonFinite,
+) -> Option<String> {
+    if value.is_finite() {
+        return None;
+    }
+    Some(match repr {
+        NonFinite::Empty => String::new(),
+        NonFinite::Name if value.is_nan() => "NaN".to_string(),
+        NonFinite::Name if value.is_sign_negative() => "-Infinity".to_string(),
+        NonFinite::Name => "Infinity".to_string(),
+        NonFinite::Token(ref token) => token.to_string(),
+    })
+}
+
 pub(crate) fn unquote(value: &Value) -> String {
     match value {
         Value::String(ref s) => s.to_owned(),
@@ -63,6 +84,12 @@ pub(crate) fn find_field<'b, S: AsRef<str>>(
         Value::Array(ref list) => {
             if let Ok(index) = field.as_ref().parse::<usize>() {
                 return list.get(index);
+            } else if let Ok(index) = field.as_ref().parse::<isize>() {
+                // Negative indices count back from the end of the array
+                return list
+                    .len()
+                    .checked_sub(index.unsigned_abs())
+                    .and_then(|index| list.get(index));
             }
         }
         _ => {}
@@ -51,7 +51,7 @@ pub enum Block {
     Text,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -76,7 +76,36 @@ pub enum RawComment {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
+    Newline,
+
+    /// Error token.
+    #[error]
+    Error,
+}
+
+/// Tokens for the content of raw blocks.
+///
+/// Raw block content is not interpreted; these tokens are emitted
+/// as `Block` tokens so that nested raw blocks are text and the
+/// block ends at the matching `{{{{/raw}}}}` tag.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Logos)]
+#[logos(extras = Extras)]
+enum RawBlock {
+    /// Open tag of a nested raw block.
+    #[regex(r"\{\{\{\{~?[\t ]*[^\s/{}~][^{}]*\}\}\}\}")]
+    Start,
+
+    /// End of a raw block.
+    #[regex(r"\{\{\{\{~?[\t ]*/")]
+    End,
+
+    /// Text token.
+    #[regex(r".")]
+    Text,
+
+    /// Newline token.
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -100,7 +129,7 @@ pub enum RawStatement {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -125,7 +154,7 @@ pub enum Comment {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -146,6 +175,10 @@ pub enum Parameters {
     #[token(r">")]
     Partial,
 
+    /// Token for a decorator instruction.
+    #[token(r"*")]
+    Decorator,
+
     /// Token for the `else` conditional keyword.
     #[token(r"else")]
     ElseKeyword,
@@ -198,6 +231,10 @@ pub enum Parameters {
     #[regex(r"(?&identifier)+=")]
     HashKey,
 
+    /// Token for block parameters, for example, `as |item index|`.
+    #[regex(r"as[ \t]+\|[ \t]*((?&identifier)+[ \t]*)+\|")]
+    BlockParams,
+
     /// Token for numeric values.
     // NOTE: Must have higher priority than identifier
     // NOTE: otherwise numbers become identifiers
@@ -220,12 +257,20 @@ pub enum Parameters {
     #[regex(r"[ \t]+")]
     WhiteSpace,
 
+    /// Token for a line comment.
+    ///
+    /// A semicolon starts a comment that runs to the end of the line,
+    /// the end of the tag or the end of a sub-expression; comments may
+    /// not contain `}`, `)` or `~`.
+    #[regex(r";[^\r\n})~]*")]
+    LineComment,
+
     /// Token for the end of a statement or block open tag.
     #[regex(r"~?\}?\}?\}\}")]
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -254,7 +299,7 @@ pub enum DoubleQuoteString {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -283,7 +328,7 @@ pub enum SingleQuoteString {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -310,7 +355,7 @@ pub enum Array {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -342,12 +387,20 @@ pub enum Link {
     #[token(r#"\]"#)]
     Escaped,
 
+    /// Escaped left bracket token.
+    #[token(r#"\["#)]
+    EscapedOpenBracket,
+
+    /// Escaped backslash token.
+    #[token(r#"\\"#)]
+    EscapedBackslash,
+
     /// End of square bracket literal.
     #[token(r"]]")]
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex(r"\r?\n")]
     Newline,
 
     /// Error token.
@@ -440,6 +493,7 @@ enum Modes<'source> {
     Block(Lex<'source, Block>),
     RawComment(Lex<'source, RawComment>),
     RawStatement(Lex<'source, RawStatement>),
+    RawBlock(Lex<'source, RawBlock>),
     Comment(Lex<'source, Comment>),
     Parameters(Lex<'source, Parameters>),
     DoubleQuoteString(Lex<'source, DoubleQuoteString>),
@@ -457,9 +511,22 @@ impl<'source> Modes<'source> {
 /// Iterator for a stream of grammar tokens.
 pub struct Lexer<'source> {
     mode: Modes<'source>,
+    // Set when the open tag of a raw block is being lexed.
+    raw_open: bool,
+    // Number of nested raw blocks inside raw block content.
+    raw_depth: usize,
 }
 
 impl<'source> Lexer<'source> {
+    /// Skip bytes at the start of the source.
+    ///
+    /// Must be called immediately after creating the lexer.
+    pub(crate) fn bump(&mut self, n: usize) {
+        if let Modes::Block(lexer) = &mut self.mode {
+            lexer.bump(n);
+        }
+    }
+
     /// Utility for switching the lexer to parameters mode.
     ///
     /// Must be called immediately after creating the lexer otherwise
@@ -499,6 +566,7 @@ impl<'source> Iterator for Lexer<'source> {
 
                 if let Some(token) = result {
                     if Block::StartRawBlock == token {
+                        self.raw_open = true;
                         self.mode = Modes::Parameters(lexer.to_owned().morph());
                     } else if Block::EndRawBlock == token {
                         self.mode = Modes::Parameters(lexer.to_owned().morph());
@@ -549,6 +617,34 @@ impl<'source> Iterator for Lexer<'source> {
                     None
                 }
             }
+            Modes::RawBlock(lexer) => {
+                let result = lexer.next();
+                let span = lexer.span();
+
+                if let Some(token) = result {
+                    let token = match token {
+                        RawBlock::Start => {
+                            self.raw_depth += 1;
+                            Block::Text
+                        }
+                        RawBlock::End if self.raw_depth > 0 => {
+                            self.raw_depth -= 1;
+                            Block::Text
+                        }
+                        RawBlock::End => {
+                            self.mode =
+                                Modes::Parameters(lexer.to_owned().morph());
+                            Block::EndRawBlock
+                        }
+                        RawBlock::Newline => Block::Newline,
+                        RawBlock::Text => Block::Text,
+                        RawBlock::Error => Block::Error,
+                    };
+                    Some(Token::Block(token, span))
+                } else {
+                    None
+                }
+            }
             Modes::Comment(lexer) => {
                 let result = lexer.next();
                 let span = lexer.span();
@@ -575,6 +671,9 @@ impl<'source> Iterator for Lexer<'source> {
                             Modes::SingleQuoteString(lexer.to_owned().morph());
                     } else if Parameters::StartArray == token {
                         self.mode = Modes::Array(lexer.to_owned().morph());
+                    } else if Parameters::End == token && self.raw_open {
+                        self.raw_open = false;
+                        self.mode = Modes::RawBlock(lexer.to_owned().morph());
                     } else if Parameters::End == token {
                         self.mode = Modes::Block(lexer.to_owned().morph());
                     }
@@ -673,6 +772,8 @@ fn normalize(tokens: Vec<Token>) -> Vec<Token> {
 pub fn lex(s: &str) -> Lexer {
     Lexer {
         mode: Modes::new(s),
+        raw_open: false,
+        raw_depth: 0,
     }
 }
 
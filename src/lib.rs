@@ -104,6 +104,23 @@
 //! let errors = registry.lint("file-name.md", "{{.bad.path}}")?;
 //! ```
 //!
+//! To also report calls to helpers and partials that are not registered
+//! use `lint_strict`:
+//!
+//! ```ignore
+//! let errors = registry.lint_strict("file-name.md", "{{uppr name}}")?;
+//! ```
+//!
+//! ## Format
+//!
+//! To print a template using normalized syntax call `format()` on a
+//! compiled template:
+//!
+//! ```ignore
+//! let template = registry.parse("file-name.md", "{{#if  foo }}{{ bar}}{{/if}}")?;
+//! let formatted = template.format(Default::default());
+//! ```
+//!
 //! ## Escape
 //!
 //! By default templates are escaped for HTML output; you can call `set_escape()`
@@ -135,6 +152,7 @@
 //!
 //! * [log](helper::log::Log) Print log messages.
 //! * [lookup](helper::lookup::Lookup) Lookup a field of an object or array.
+//! * [has](helper::lookup::Has) Determine if a path exists.
 //! * [#if](helper::if::If) Conditional block helper.
 //! * [#unless](helper::unless::Unless) Negated conditional block helper.
 //! * [#each](helper::each::Each) Iterate arrays and objects.
@@ -143,11 +161,29 @@
 //! Some useful extra helpers are also enabled by default:
 //!
 //! * [json](helper::json::Json) Convert values to JSON strings.
+//! * [repeat](helper::string::Repeat) Repeat a string or inner template.
+//! * [len](helper::string::Len) Length of a string, array or object.
+//! * [#indent](helper::string::Indent) Indent the lines of an inner template.
+//! * [titlecase](helper::string::TitleCase) Convert a string to title case.
+//! * [classnames](helper::string::ClassNames) Compose conditional class names.
+//! * [#trim](helper::string::Trim) Trim whitespace from an inner template.
+//! * [first](helper::array::First) First element(s) of an array.
+//! * [last](helper::array::Last) Last element(s) of an array.
+//! * [range](helper::array::Range) Create an array of integers.
+//! * [#chunk](helper::array::Chunk) Iterate an array in chunks.
+//! * [is_array](helper::types::IsType) Type predicates (`is_array`, `is_object`, etc).
+//! * [number](helper::number::Number) Format numbers with a precision and separator.
+//! * [percent](helper::number::Percent) Format a ratio as a percentage.
+//! * [filesize](helper::number::FileSize) Format a byte count as a file size.
+//! * [urlencode](helper::url::UrlEncode) Percent-encode a string.
+//! * [urldecode](helper::url::UrlDecode) Decode a percent-encoded string.
 //! * [and](helper::logical::And) Logical boolean AND operation.
 //! * [or](helper::logical::Or) Logical boolean OR operation.
 //! * [not](helper::logical::Not) Logical boolean NOT operation.
+//! * [truthy](helper::logical::Truthy) Convert a value to a boolean.
+//! * [falsy](helper::logical::Falsy) Negated boolean for a value.
 //!
-//! Numerical comparison helpers:
+//! Comparison helpers for numbers, strings and arrays:
 //!
 //! * [eq](helper::comparison::Equal) Test for equality.
 //! * [ne](helper::comparison::NotEqual) Test for inequality.
@@ -156,6 +192,10 @@
 //! * [lte](helper::comparison::LessThanEqual) Test for less than or equal to.
 //! * [gte](helper::comparison::GreaterThanEqual) Test for greater than or equal to.
 //!
+//! The [env](helper::env::Env) helper reads environment variables; it
+//! requires the `env-helper` feature, is not a builtin and must be
+//! added to a registry explicitly.
+//!
 //! To add a helper to the registry use `helpers_mut()`:
 //!
 //! ```ignore
@@ -191,11 +231,19 @@
 //! `[[/path/to/page|Link Label|Alternative Title]]` this is passed as the third
 //! argument to the link helper.
 //!
+//! To rewrite the `href` before the link helper is invoked (for example to
+//! resolve a page name to a URL) assign a link resolver function:
+//!
+//! ```ignore
+//! registry.handlers_mut().link_resolver =
+//!     Some(Box::new(|href: &str| format!("/wiki/{}", href)));
+//! ```
+//!
 //! If this feature is disabled or no handler is defined the link is
 //! rendered to the output as text.
 //!
-//! Links do not accept new lines; to include a new line, vertical pipe or right square bracket
-//! it must be preceeded by a backslash, eg: `\n`, `\|` or `\]`.
+//! Links do not accept new lines; to include a new line, vertical pipe, square bracket
+//! or backslash it must be preceeded by a backslash, eg: `\n`, `\|`, `\]`, `\[` or `\\`.
 //!
 //! To ignore a wiki-style link from processing prefix it with a backslash `\[[Plain Text]]` and
 //! it will be rendered as text without the backslash.
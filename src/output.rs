@@ -1,7 +1,13 @@
 //! Trait and type for rendering to destinations.
-use std::io::{Result, Write};
+use std::fmt;
+use std::io::{Error, ErrorKind, Result, Write};
 
 /// Trait for types that we can render to.
+///
+/// The renderer calls `flush()` from the `Write` super trait when a
+/// render completes and optionally after each top-level statement
+/// (see [set_flush_statements()](crate::Registry#method.set_flush_statements))
+/// so buffered writers can deliver output incrementally.
 pub trait Output: Write {
     /// Convenience function as we are typically writing string slices.
     fn write_str(&mut self, s: &str) -> Result<usize>;
@@ -12,6 +18,18 @@ pub struct Writer<W: Write> {
     writer: W,
 }
 
+impl<W: Write> Writer<W> {
+    /// Create a new output for a writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Unwrap this output returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
 impl<W: Write> Output for Writer<W> {
     fn write_str(&mut self, s: &str) -> Result<usize> {
         self.writer.write(s.as_bytes())
@@ -28,6 +46,45 @@ impl<W: Write> Write for Writer<W> {
     }
 }
 
+/// Output type that wraps a `fmt::Write` writer.
+///
+/// Use this to append to an existing `String` or to render
+/// directly to a `Formatter` in a `Display` implementation.
+pub struct FmtOutput<W: fmt::Write> {
+    writer: W,
+}
+
+impl<W: fmt::Write> FmtOutput<W> {
+    /// Create a new output for a formatting writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Unwrap this output returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: fmt::Write> Output for FmtOutput<W> {
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        self.writer.write_str(s).map_err(Error::other)?;
+        Ok(s.len())
+    }
+}
+
+impl<W: fmt::Write> Write for FmtOutput<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let s = std::str::from_utf8(buf)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        Output::write_str(self, s)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 /// Output type that buffers into a string.
 ///
 /// Call `into()` to access the result after
@@ -71,3 +128,89 @@ impl Write for StringOutput {
         Ok(())
     }
 }
+
+/// Output type that forwards string chunks to a callback.
+///
+/// Content is buffered until the output is flushed and then the
+/// content written since the previous flush is passed to the callback
+/// so it can be delivered while the rest of the template renders.
+///
+/// An error returned by the callback is propagated to the renderer
+/// which stops rendering; use this to abort when a client disconnects.
+pub struct ChunkOutput<F>
+where
+    F: FnMut(String) -> Result<()>,
+{
+    buffer: StringOutput,
+    callback: F,
+}
+
+impl<F> ChunkOutput<F>
+where
+    F: FnMut(String) -> Result<()>,
+{
+    /// Create a new chunk output for a callback.
+    pub fn new(callback: F) -> Self {
+        Self {
+            buffer: StringOutput::new(),
+            callback,
+        }
+    }
+}
+
+impl<F> Output for ChunkOutput<F>
+where
+    F: FnMut(String) -> Result<()>,
+{
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        self.buffer.write_str(s)
+    }
+}
+
+impl<F> Write for ChunkOutput<F>
+where
+    F: FnMut(String) -> Result<()>,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let chunk: String =
+            std::mem::replace(&mut self.buffer, StringOutput::new()).into();
+        if !chunk.is_empty() {
+            (self.callback)(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+/// Output type that discards everything written to it.
+///
+/// Useful to check that templates render without errors when
+/// the output is not needed.
+#[derive(Default)]
+pub struct NullOutput;
+
+impl NullOutput {
+    /// Create a new output that discards the content.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Output for NullOutput {
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        Ok(s.len())
+    }
+}
+
+impl Write for NullOutput {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 //! Abstract syntax tree node types.
-use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Range;
 
+use indexmap::IndexMap;
 use serde_json::Value;
 
 use crate::{parser::iter::BranchIter, trim::TrimHint};
@@ -141,6 +142,20 @@ impl<'source> Node<'source> {
         }
     }
 
+    /// The byte range of the template source for this node.
+    pub fn span(&self) -> Range<usize> {
+        match *self {
+            Self::Document(ref n) => 0..n.as_str().len(),
+            Self::Text(ref n) => n.span().clone(),
+            Self::RawStatement(ref n)
+            | Self::RawComment(ref n)
+            | Self::Comment(ref n) => n.span(),
+            Self::Statement(ref n) => n.span(),
+            Self::Block(ref n) => n.span(),
+            Self::Link(ref n) => n.span(),
+        }
+    }
+
     /// Iterate descendants of documents and blocks.
     pub fn into_iter<'a>(&'a self) -> BranchIter<'a> {
         BranchIter::new(self)
@@ -222,6 +237,21 @@ impl<'source> Text<'source> {
     ) -> Self {
         Self { source, span, line }
     }
+
+    /// The byte range for this text.
+    pub fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    /// Mutable byte range for this text.
+    pub fn span_mut(&mut self) -> &mut Range<usize> {
+        &mut self.span
+    }
+
+    /// Set the source string for this text.
+    pub(crate) fn set_source(&mut self, source: &'source str) {
+        self.source = source;
+    }
 }
 
 impl<'source> Lines for Text<'source> {
@@ -285,6 +315,18 @@ impl<'source> TextBlock<'source> {
             close,
         }
     }
+
+    /// The byte range for this text block including the open
+    /// and close tags.
+    pub fn span(&self) -> Range<usize> {
+        self.open.start..self.close.end
+    }
+
+    /// Set the source string for this text block.
+    pub(crate) fn set_source(&mut self, source: &'source str) {
+        self.source = source;
+        self.text.set_source(source);
+    }
 }
 
 impl<'source> Slice<'source> for TextBlock<'source> {
@@ -513,6 +555,8 @@ impl<'source> Path<'source> {
     ///
     /// A path is absolute when it begins with a slash (/); 
     /// paths that start with a period (.) delimiter are illegal.
+    ///
+    /// Absolute paths are resolved from the root of the template data.
     pub fn absolute(&self) -> bool {
         self.absolute 
     }
@@ -589,6 +633,49 @@ impl<'source> Path<'source> {
             && self.components.first().unwrap().kind
                 == ComponentType::Identifier;
     }
+
+    /// Determine if this path only contains identifiers,
+    /// for example, `foo` or `ui/button`.
+    pub fn is_identifiers(&self) -> bool {
+        self.parents == 0
+            && !self.explicit
+            && !self.components.is_empty()
+            && self
+                .components
+                .iter()
+                .all(|c| c.kind == ComponentType::Identifier)
+    }
+
+    /// Get a normalized representation of this path.
+    ///
+    /// Components are joined using a period regardless of the
+    /// delimiters in the template source, parent references are
+    /// written as `../` and an explicit `./` is written as `this`
+    /// so that `./foo`, `this/foo` and `this.foo` are all `this.foo`.
+    ///
+    /// Absolute paths keep the leading slash and raw identifiers
+    /// use the unescaped value.
+    pub fn to_dotted(&self) -> String {
+        let mut result = String::new();
+        if self.absolute {
+            result.push('/');
+        }
+        result.push_str(&"../".repeat(self.parents as usize));
+        let names = self
+            .components
+            .iter()
+            .filter(|c| c.kind != ComponentType::Delimiter)
+            .map(|c| {
+                if c.is_explicit_dot_slash() {
+                    "this"
+                } else {
+                    c.as_value()
+                }
+            })
+            .collect::<Vec<_>>();
+        result.push_str(&names.join("."));
+        result
+    }
 }
 
 impl<'source> Slice<'source> for Path<'source> {
@@ -789,19 +876,24 @@ impl Default for CallTarget<'_> {
 /// A call has zero or more arguments and optional hash parameters.
 ///
 /// The partial flag is used to indicate that this call should be
-/// rendered as a partial.
+/// rendered as a partial and the decorator flag indicates a decorator
+/// such as an inline partial definition (`{{#*inline "name"}}`).
 #[derive(Default, Eq, PartialEq)]
 pub struct Call<'source> {
     // Raw source input.
     source: &'source str,
     partial: bool,
+    decorator: bool,
     conditional: bool,
+    escaped: bool,
     open: Range<usize>,
     close: Option<Range<usize>>,
     target: CallTarget<'source>,
     arguments: Vec<ParameterValue<'source>>,
-    parameters: HashMap<&'source str, ParameterValue<'source>>,
+    parameters: IndexMap<&'source str, ParameterValue<'source>>,
+    block_params: Vec<&'source str>,
     line: Range<usize>,
+    indent: u32,
 }
 
 impl<'source> Call<'source> {
@@ -817,13 +909,17 @@ impl<'source> Call<'source> {
         Self {
             source,
             partial: false,
+            decorator: false,
             conditional: false,
+            escaped: true,
             open,
             close: None,
             target: CallTarget::Path(Path::new(source, 0..0, 0..0)),
             arguments: Vec::new(),
-            parameters: HashMap::new(),
+            parameters: IndexMap::new(),
+            block_params: Vec::new(),
             line,
+            indent: 0,
         }
     }
 
@@ -866,13 +962,24 @@ impl<'source> Call<'source> {
         self.parameters.insert(key, val);
     }
 
-    /// Get the map of hash parameters.
+    /// Get the map of hash parameters in template source order.
     pub fn parameters(
         &self,
-    ) -> &HashMap<&'source str, ParameterValue<'source>> {
+    ) -> &IndexMap<&'source str, ParameterValue<'source>> {
         &self.parameters
     }
 
+    /// Add a block parameter name to this call.
+    pub fn add_block_param(&mut self, name: &'source str) {
+        self.block_params.push(name);
+    }
+
+    /// Get the names of the block parameters declared using
+    /// `as |name|` in the open tag of a block.
+    pub fn block_params(&self) -> &[&'source str] {
+        &self.block_params
+    }
+
     /// Determine if this call has the partial flag.
     pub fn is_partial(&self) -> bool {
         self.partial
@@ -883,6 +990,34 @@ impl<'source> Call<'source> {
         self.partial = partial;
     }
 
+    /// The indentation of a standalone partial.
+    ///
+    /// When a partial is the only content on a line the whitespace
+    /// before the partial is applied to each line of the partial output.
+    pub fn indent(&self) -> Option<&'source str> {
+        if self.indent == 0 {
+            return None;
+        }
+        let end = self.open.start;
+        Some(&self.source[end - self.indent as usize..end])
+    }
+
+    /// Set the length in bytes of the whitespace before a standalone
+    /// partial that is used as the indentation.
+    pub(crate) fn set_indent(&mut self, length: usize) {
+        self.indent = u32::try_from(length).unwrap_or(0);
+    }
+
+    /// Determine if this call has the decorator flag.
+    pub fn is_decorator(&self) -> bool {
+        self.decorator
+    }
+
+    /// Set the decorator flag.
+    pub fn set_decorator(&mut self, decorator: bool) {
+        self.decorator = decorator;
+    }
+
     /// Determine if this call has a conditional flag (the `else` keyword).
     pub fn is_conditional(&self) -> bool {
         self.conditional
@@ -894,9 +1029,16 @@ impl<'source> Call<'source> {
     }
 
     /// Determine if the content of this call should be escaped.
+    ///
+    /// The parser clears this flag for triple-stash statements
+    /// (`{{{var}}}`) and the open tag of raw blocks.
     pub fn is_escaped(&self) -> bool {
-        // FIXME: ensure this is not `true` for raw blocks!
-        !self.open().starts_with("{{{")
+        self.escaped
+    }
+
+    /// Set the escaped flag.
+    pub fn set_escaped(&mut self, escaped: bool) {
+        self.escaped = escaped;
     }
 
     fn trim_before(&self) -> bool {
@@ -906,6 +1048,11 @@ impl<'source> Call<'source> {
     fn trim_after(&self) -> bool {
         self.close().starts_with(WHITESPACE)
     }
+
+    /// Set the source string for this call.
+    pub(crate) fn set_source(&mut self, source: &'source str) {
+        self.source = source;
+    }
 }
 
 impl<'source> Slice<'source> for Call<'source> {
@@ -971,11 +1118,14 @@ impl fmt::Debug for Call<'_> {
         f.debug_struct("Call")
             .field("source", &self.as_str())
             .field("partial", &self.partial)
+            .field("decorator", &self.decorator)
+            .field("escaped", &self.escaped)
             .field("open", &self.open)
             .field("close", &self.close)
             .field("target", &self.target)
             .field("arguments", &self.arguments)
             .field("parameters", &self.parameters)
+            .field("block_params", &self.block_params)
             .finish()
     }
 }
@@ -1072,11 +1222,13 @@ impl<'source> Block<'source> {
     /// The name of this block extracted from the call target.
     ///
     /// This will only be available if the call target is a path
-    /// and the path is a simple identifier.
+    /// and the path is a simple identifier or `@partial-block`.
     pub fn name(&self) -> Option<&'source str> {
         match self.call.target() {
             CallTarget::Path(ref path) => {
-                if path.is_simple() {
+                if path.as_str() == "@partial-block" {
+                    Some(path.as_str())
+                } else if path.is_simple() {
                     let id = path.components().first().unwrap();
                     Some(id.as_str())
                 } else {
@@ -1092,6 +1244,18 @@ impl<'source> Block<'source> {
         self.raw
     }
 
+    /// The source text between the open and close tags of this block.
+    ///
+    /// Includes the source of any conditional branches; when the
+    /// block has not been closed the empty string is returned.
+    pub fn inner(&self) -> &'source str {
+        if let Some(ref close) = self.close {
+            &self.source[self.call.span().end..close.start]
+        } else {
+            ""
+        }
+    }
+
     /// Add a condition to this block.
     pub fn add_condition(&mut self, condition: Block<'source>) {
         self.close_condition(condition.call.open.clone());
@@ -1126,6 +1290,22 @@ impl<'source> Block<'source> {
         &self.nodes
     }
 
+    /// Mutable collection of nodes for this block.
+    pub(crate) fn nodes_mut(&mut self) -> &mut Vec<Node<'source>> {
+        &mut self.nodes
+    }
+
+    /// Mutable list of conditional blocks.
+    pub(crate) fn conditions_mut(&mut self) -> &mut Vec<Node<'source>> {
+        &mut self.conditionals
+    }
+
+    /// Set the source string for this block and the block call.
+    pub(crate) fn set_source(&mut self, source: &'source str) {
+        self.source = source;
+        self.call.set_source(source);
+    }
+
     /// The trim hint for the close tag.
     pub fn trim_close(&self) -> TrimHint {
         TrimHint {
@@ -1134,17 +1314,16 @@ impl<'source> Block<'source> {
         }
     }
 
+    // The close for a conditional is the open delimiter of the next
+    // conditional so that whitespace trim markers are respected
     fn close_condition(&mut self, span: Range<usize>) {
         if !self.conditionals.is_empty() {
-            if span.start > 0 {
-                let close = span.start - 1..span.start;
-                let mut last = self.conditionals.last_mut().unwrap();
-                match &mut last {
-                    Node::Block(ref mut condition) => {
-                        condition.close = Some(close);
-                    }
-                    _ => {}
+            let mut last = self.conditionals.last_mut().unwrap();
+            match &mut last {
+                Node::Block(ref mut condition) => {
+                    condition.close = Some(span);
                 }
+                _ => {}
             }
         }
     }
@@ -1316,6 +1495,11 @@ impl<'source> Link<'source> {
         }
     }
 
+    /// Set the source string for this link.
+    pub(crate) fn set_source(&mut self, source: &'source str) {
+        self.source = source;
+    }
+
     /// Get the link href.
     ///
     /// If an owned value has been set it is preferred.
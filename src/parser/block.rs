@@ -1,7 +1,7 @@
 use std::ops::Range;
 
 use crate::{
-    error::{ErrorInfo, SyntaxError},
+    error::{ErrorInfo, SourcePos, SyntaxError},
     lexer::{self, Lexer, Token},
     parser::{
         ast::{Block, Element, Lines, Node, Slice, Text, TextBlock},
@@ -57,6 +57,10 @@ pub(crate) fn text_until<'source>(
 }
 
 /// Parse a raw block `{{{{raw}}}}{{{{/raw}}}}`.
+///
+/// The block may use any name but the names of the open
+/// and close tags must match. The content is not interpreted,
+/// balanced nested raw blocks are included in the text.
 pub(crate) fn raw<'source>(
     source: &'source str,
     lexer: &mut Lexer<'source>,
@@ -201,12 +205,17 @@ pub(crate) fn raw_statement<'source>(
 }
 
 /// Parse a comment block `{{! comment }}`.
+///
+/// The comment is always parsed; when it looks like it was closed
+/// early by a statement that was meant to be commented out a
+/// `CommentStatement` error is returned alongside the node so that
+/// it can be reported as a lint diagnostic.
 pub(crate) fn comment<'source>(
     source: &'source str,
     lexer: &mut Lexer<'source>,
     state: &mut ParseState,
     span: Range<usize>,
-) -> SyntaxResult<Node<'source>> {
+) -> SyntaxResult<(Node<'source>, Option<SyntaxError>)> {
     let end = |t: &Token| match t {
         Token::Comment(lex, _) => match lex {
             lexer::Comment::End => true,
@@ -215,10 +224,37 @@ pub(crate) fn comment<'source>(
         _ => false,
     };
 
+    let open = span.end;
+    let line = *state.line();
     let wrap = |t: TextBlock<'source>| Node::Comment(t);
     let maybe_node = text_until(source, lexer, state, span, &end, &wrap);
-    if let Some((node, _)) = maybe_node {
-        Ok(node)
+    if let Some((node, next_token)) = maybe_node {
+        // When the comment contains an open tag and the text after the
+        // comment contains a close tag before any other open tag the
+        // comment was terminated early by the close tag of a statement
+        // that was intended to be commented out
+        let (close, after) = next_token
+            .map(|t| (t.span().start, t.span().end))
+            .unwrap_or((open, open));
+        let rest = &source[after..];
+        let stray = match (rest.find("}}"), rest.find("{{")) {
+            (Some(end), Some(start)) => end < start,
+            (Some(_), None) => true,
+            _ => false,
+        };
+        let mut note = None;
+        if let (true, Some(offset)) = (stray, source[open..close].find("{{")) {
+            let prefix = &source[open..open + offset];
+            let lines = prefix.matches('\n').count();
+            let pos = SourcePos(line + lines, open + offset);
+            let notes =
+                vec!["use a raw comment {{!-- --}} to comment out statements"
+                    .to_string()];
+            note = Some(SyntaxError::CommentStatement(
+                ErrorInfo::new(source, state.file_name(), pos, notes).into(),
+            ));
+        }
+        Ok((node, note))
     } else {
         Err(SyntaxError::CommentNotTerminated(
             ErrorInfo::from((source, state)).into(),
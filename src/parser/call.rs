@@ -165,6 +165,20 @@ fn value<'source>(
     }
 }
 
+/// Add the names declared by a block parameters token to a call.
+fn block_params<'source>(
+    source: &'source str,
+    call: &mut Call<'source>,
+    span: Range<usize>,
+) {
+    let names = source[span.start + 2..span.end]
+        .trim_start()
+        .trim_matches('|');
+    for name in names.split_whitespace() {
+        call.add_block_param(name);
+    }
+}
+
 fn key_value<'source>(
     source: &'source str,
     lexer: &mut Lexer<'source>,
@@ -177,7 +191,13 @@ fn key_value<'source>(
     let key = &source[span.start..span.end - 1];
     let mut next: Option<Token> = None;
 
-    // FIXME: support absolute path values (paths with leading slash) for hash parameters
+    if call.parameters().contains_key(key) {
+        *state.byte_mut() = span.start;
+        let notes = vec![format!("key '{}' is already defined", key)];
+        return Err(SyntaxError::DuplicateHashKey(
+            ErrorInfo::from((source, state, notes)).into(),
+        ));
+    }
 
     // Consume the first value
     if let Some(token) = lexer.next() {
@@ -199,7 +219,9 @@ fn key_value<'source>(
     while let Some(token) = next {
         match token {
             Token::Parameters(lex, span) => match &lex {
-                Parameters::WhiteSpace | Parameters::Newline => {
+                Parameters::WhiteSpace
+                | Parameters::LineComment
+                | Parameters::Newline => {
                     if lex == Parameters::Newline {
                         *state.line_mut() += 1;
                     }
@@ -214,6 +236,9 @@ fn key_value<'source>(
                         context,
                     );
                 }
+                Parameters::BlockParams => {
+                    block_params(source, call, span);
+                }
                 Parameters::End => {
                     call.exit(span);
                     return Ok(None);
@@ -257,7 +282,9 @@ fn arguments<'source>(
         match token {
             Token::Parameters(lex, span) => {
                 match &lex {
-                    Parameters::WhiteSpace | Parameters::Newline => {
+                    Parameters::WhiteSpace
+                    | Parameters::LineComment
+                    | Parameters::Newline => {
                         if lex == Parameters::Newline {
                             *state.line_mut() += 1;
                         }
@@ -271,6 +298,11 @@ fn arguments<'source>(
                             ErrorInfo::from((source, state)).into(),
                         ))
                     }
+                    Parameters::Decorator => {
+                        return Err(SyntaxError::DecoratorPosition(
+                            ErrorInfo::from((source, state)).into(),
+                        ))
+                    }
                     Parameters::ElseKeyword => {}
                     // Path components
                     Parameters::ExplicitThisKeyword
@@ -299,6 +331,13 @@ fn arguments<'source>(
                             context,
                         );
                     }
+                    Parameters::BlockParams => {
+                        block_params(source, call, span);
+                        let next = lexer.next();
+                        return arguments(
+                            source, lexer, state, call, next, context,
+                        );
+                    }
                     // Open a nested call
                     Parameters::StartSubExpression => {
                         let (value, token) =
@@ -323,24 +362,6 @@ fn arguments<'source>(
                             source, lexer, state, call, token, context,
                         );
                     }
-                    /*
-                    Parameters::PathDelimiter => {
-                        let leading_delimiter = &source[span.start..span.end];
-                        if leading_delimiter == "/" {
-                            // Consume as an absolute path
-                            let (value, token) =
-                                value(source, lexer, state, (lex, span), true)?;
-                            call.add_argument(value);
-                            return arguments(
-                                source, lexer, state, call, token, context,
-                            );
-                        } else {
-                            return Err(SyntaxError::PathDelimiterNotAllowed(
-                                ErrorInfo::from((source, state)).into(),
-                            ))
-                        }
-                    }
-                    */
                     Parameters::EndSubExpression => {
                         if context == CallContext::SubExpr {
                             call.exit(span);
@@ -389,7 +410,9 @@ fn target<'source>(
         match token {
             Token::Parameters(lex, span) => {
                 match &lex {
-                    Parameters::WhiteSpace | Parameters::Newline => {
+                    Parameters::WhiteSpace
+                    | Parameters::LineComment
+                    | Parameters::Newline => {
                         if lex == Parameters::Newline {
                             *state.line_mut() += 1;
                         }
@@ -459,7 +482,7 @@ fn target<'source>(
     Ok(None)
 }
 
-/// Parse the partial and conditional flags.
+/// Parse the partial, decorator and conditional flags.
 fn flags<'source>(
     _source: &'source str,
     lexer: &mut Lexer<'source>,
@@ -470,7 +493,9 @@ fn flags<'source>(
     while let Some(token) = next {
         match token {
             Token::Parameters(lex, span) => match &lex {
-                Parameters::WhiteSpace | Parameters::Newline => {
+                Parameters::WhiteSpace
+                | Parameters::LineComment
+                | Parameters::Newline => {
                     if lex == Parameters::Newline {
                         *state.line_mut() += 1;
                     }
@@ -479,6 +504,10 @@ fn flags<'source>(
                     call.set_partial(true);
                     return Ok(lexer.next());
                 }
+                Parameters::Decorator => {
+                    call.set_decorator(true);
+                    return Ok(lexer.next());
+                }
                 Parameters::ElseKeyword => {
                     call.set_conditional(true);
                     return Ok(lexer.next());
@@ -523,11 +552,22 @@ pub(crate) fn parse<'source>(
     state: &mut ParseState,
     open: Range<usize>,
     // TODO: use this to determine whether `else` keyword is legal
-    _parse_context: CallParseContext,
+    parse_context: CallParseContext,
 ) -> SyntaxResult<Call<'source>> {
     *state.byte_mut() = open.end;
 
     let mut call = Call::new(source, open, state.line_range());
+
+    // Raw block content is never escaped otherwise the open
+    // tag determines whether a statement is escaped
+    let escaped = match parse_context {
+        CallParseContext::Raw => false,
+        CallParseContext::Block => true,
+        CallParseContext::Statement | CallParseContext::ScopeStatement => {
+            !call.open().starts_with("{{{")
+        }
+    };
+    call.set_escaped(escaped);
     let next = lexer.next();
     let next = flags(source, lexer, state, &mut call, next)?;
 
@@ -0,0 +1,133 @@
+//! Custom delimiters for templates embedded in other languages.
+use crate::{error::Error, parser::ast::Node, Result};
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+const RAW_COMMENT: &str = "!--";
+const RAW_COMMENT_END: &str = "--";
+
+/// Replaces braces in text so they are not lexed as tags.
+const TEXT_OPEN_BRACE: char = '\u{0}';
+const TEXT_CLOSE_BRACE: char = '\u{1}';
+
+/// Length in bytes of the default delimiters.
+const LENGTH: usize = 2;
+
+enum State {
+    Text,
+    Tag,
+    RawComment,
+}
+
+/// Delimiters that replace the default `{{` and `}}`.
+///
+/// Templates are rewritten to use the default delimiters before they
+/// are parsed and the text nodes are then restored to refer to the
+/// original source. Both delimiters must be exactly two bytes so that
+/// byte offsets and line numbers in error messages match the original
+/// source; error messages display the default delimiters.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Delimiters {
+    open: String,
+    close: String,
+}
+
+impl Delimiters {
+    /// Create custom delimiters.
+    pub fn new(open: &str, close: &str) -> Result<Self> {
+        if open.len() != LENGTH || close.len() != LENGTH {
+            return Err(Error::InvalidDelimiters(
+                open.to_string(),
+                close.to_string(),
+            ));
+        }
+        Ok(Self {
+            open: open.to_string(),
+            close: close.to_string(),
+        })
+    }
+
+    /// The open delimiter.
+    pub fn open(&self) -> &str {
+        &self.open
+    }
+
+    /// The close delimiter.
+    pub fn close(&self) -> &str {
+        &self.close
+    }
+
+    /// Rewrite a template to use the default delimiters.
+    ///
+    /// Braces in text are replaced so that they are not interpreted
+    /// as part of a tag; the returned string always has the same
+    /// length as the source.
+    pub(crate) fn rewrite(&self, source: &str) -> String {
+        let mut result = String::with_capacity(source.len());
+        let mut state = State::Text;
+        let mut rest = source;
+        while let Some(c) = rest.chars().next() {
+            match state {
+                State::Text if rest.starts_with(&self.open) => {
+                    result.push_str(OPEN);
+                    rest = &rest[LENGTH..];
+                    state = if rest.starts_with(RAW_COMMENT) {
+                        State::RawComment
+                    } else {
+                        State::Tag
+                    };
+                    continue;
+                }
+                State::Tag if rest.starts_with(&self.close) => {
+                    result.push_str(CLOSE);
+                    rest = &rest[LENGTH..];
+                    state = State::Text;
+                    continue;
+                }
+                State::RawComment
+                    if rest.starts_with(RAW_COMMENT_END)
+                        && rest[RAW_COMMENT_END.len()..]
+                            .starts_with(&self.close) =>
+                {
+                    result.push_str(RAW_COMMENT_END);
+                    result.push_str(CLOSE);
+                    rest = &rest[RAW_COMMENT_END.len() + LENGTH..];
+                    state = State::Text;
+                    continue;
+                }
+                State::Text if c == '{' => result.push(TEXT_OPEN_BRACE),
+                State::Text if c == '}' => result.push(TEXT_CLOSE_BRACE),
+                _ => result.push(c),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        result
+    }
+}
+
+/// Restore nodes to refer to the original source.
+pub(crate) fn restore<'source>(node: &mut Node<'source>, source: &'source str) {
+    match node {
+        Node::Document(ref mut doc) => {
+            doc.0 = source;
+            for child in doc.nodes_mut() {
+                restore(child, source);
+            }
+        }
+        Node::Block(ref mut block) => {
+            block.set_source(source);
+            for child in block.nodes_mut() {
+                restore(child, source);
+            }
+            for condition in block.conditions_mut() {
+                restore(condition, source);
+            }
+        }
+        Node::Text(ref mut text) => text.set_source(source),
+        Node::RawStatement(ref mut text)
+        | Node::RawComment(ref mut text)
+        | Node::Comment(ref mut text) => text.set_source(source),
+        Node::Link(ref mut link) => link.set_source(source),
+        Node::Statement(ref mut call) => call.set_source(source),
+    }
+}
@@ -0,0 +1,361 @@
+//! Format templates by printing the syntax tree using normalized syntax.
+use std::ops::Range;
+
+use crate::{
+    lexer::{self, Parameters, Token},
+    parser::ast::{
+        Block, Call, CallTarget, ComponentType, Element, Node, ParameterValue,
+        Path, RawIdType, Slice,
+    },
+    trim::TrimHint,
+};
+
+/// Options for formatting templates.
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// Add a space between the delimiters and the content of statements
+    /// and partials, for example, `{{ foo }}` rather than `{{foo}}`.
+    ///
+    /// Block tags and tags with whitespace control are not padded.
+    pub padding: bool,
+    /// Indent lines inside blocks by this number of spaces relative
+    /// to the line containing the open tag.
+    ///
+    /// When not set the whitespace in text is not modified.
+    pub indent: Option<usize>,
+}
+
+/// Format a node and all descendants.
+///
+/// Tags are printed with a single space between the call target,
+/// arguments and hash parameters; whitespace control markers are
+/// placed directly inside the delimiters and path delimiters are
+/// written as a period. Line comments inside a tag are moved to the
+/// end of the tag.
+///
+/// Text, comments, raw statements, links and the content of raw blocks
+/// are written unchanged except for indentation of text inside blocks
+/// when the `indent` option is set. Templates compiled with custom
+/// delimiters are printed using the default delimiters.
+pub fn format(node: &Node<'_>, options: &FormatOptions) -> String {
+    let mut formatter = Formatter {
+        options,
+        source: node.source(),
+        offset: 0,
+        out: String::new(),
+        indents: Vec::new(),
+        pending: false,
+    };
+    formatter.node(node);
+    formatter.out
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+    source: &'a str,
+    // End of the source consumed so far; the parser does not create
+    // text nodes for whitespace around standalone block tags
+    offset: usize,
+    out: String,
+    // Indentation for the lines containing the open tags of the
+    // blocks being formatted
+    indents: Vec<String>,
+    // Text ended at the start of a line that needs indentation
+    pending: bool,
+}
+
+impl Formatter<'_> {
+    fn node(&mut self, node: &Node<'_>) {
+        match node {
+            Node::Document(ref doc) => {
+                for child in doc.nodes() {
+                    self.node(child);
+                }
+                self.source_until(self.source.len());
+            }
+            Node::Text(ref text) => {
+                self.source_until(text.span().start);
+                self.text(text.as_str());
+                self.offset = text.span().end;
+            }
+            Node::Statement(ref call) => {
+                self.source_until(call.span().start);
+                self.line_start(false);
+                let (open, close) = if call.is_escaped() {
+                    ("{{", "}}")
+                } else {
+                    ("{{{", "}}}")
+                };
+                let sigil = if call.is_partial() { ">" } else { "" };
+                let content = self.content(call);
+                self.tag(open, close, node.trim(), sigil, &content, true);
+                self.offset = call.span().end;
+            }
+            Node::Block(ref block) => self.block(node, block),
+            Node::RawStatement(ref text)
+            | Node::RawComment(ref text)
+            | Node::Comment(ref text) => {
+                self.source_until(text.span().start);
+                self.line_start(false);
+                self.out.push_str(text.as_str());
+                self.offset = text.span().end;
+            }
+            Node::Link(ref link) => {
+                self.source_until(link.span().start);
+                self.line_start(false);
+                self.out.push_str(link.as_str());
+                self.offset = link.span().end;
+            }
+        }
+    }
+
+    fn block(&mut self, node: &Node<'_>, block: &Block<'_>) {
+        self.source_until(block.open_span().start);
+        self.line_start(false);
+
+        let call = block.call();
+        let close = block.close_span().clone().unwrap_or_else(|| {
+            let end = block.span().end;
+            end..end
+        });
+        let name = close_name(block);
+
+        if block.is_raw() {
+            let content = self.content(call);
+            self.tag("{{{{", "}}}}", node.trim(), "", &content, false);
+            self.out
+                .push_str(&self.source[call.span().end..close.start]);
+            self.tag("{{{{", "}}}}", block.trim_close(), "/", name, false);
+            self.offset = close.end;
+            return;
+        }
+
+        let sigil = if call.is_partial() {
+            "#>"
+        } else if call.is_decorator() {
+            "#*"
+        } else {
+            "#"
+        };
+
+        let base = self.line_indent();
+        let content = self.content(call);
+        self.tag("{{", "}}", node.trim(), sigil, &content, false);
+        self.offset = call.span().end;
+
+        self.indents.push(base);
+        for child in block.nodes() {
+            self.node(child);
+        }
+        for condition in block.conditions() {
+            if let Node::Block(ref branch) = condition {
+                self.source_until(branch.call().span().start);
+                self.line_start(true);
+                let mut content = String::from("else");
+                let call = self.content(branch.call());
+                if !call.is_empty() {
+                    content.push(' ');
+                    content.push_str(&call);
+                }
+                self.tag("{{", "}}", condition.trim(), "", &content, false);
+                self.offset = branch.call().span().end;
+                for child in branch.nodes() {
+                    self.node(child);
+                }
+            }
+        }
+        self.source_until(close.start);
+        self.line_start(true);
+        self.indents.pop();
+
+        self.tag("{{", "}}", block.trim_close(), "/", name, false);
+        self.offset = close.end;
+    }
+
+    fn tag(
+        &mut self,
+        open: &str,
+        close: &str,
+        trim: TrimHint,
+        sigil: &str,
+        content: &str,
+        pad: bool,
+    ) {
+        // Whitespace control markers must be adjacent to the content
+        let pad = pad && self.options.padding && !trim.before && !trim.after;
+        self.out.push_str(open);
+        if trim.before {
+            self.out.push('~');
+        }
+        self.out.push_str(sigil);
+        if pad || sigil.ends_with('>') {
+            self.out.push(' ');
+        }
+        self.out.push_str(content);
+        if pad {
+            self.out.push(' ');
+        }
+        if trim.after {
+            self.out.push('~');
+        }
+        self.out.push_str(close);
+    }
+
+    /// Content for the tag of a call including any line comments.
+    fn content(&self, call: &Call<'_>) -> String {
+        let mut content = call_content(call);
+        for comment in self.comments(call.span().clone()) {
+            if !content.is_empty() {
+                content.push(' ');
+            }
+            content.push_str(comment);
+        }
+        content
+    }
+
+    /// Line comments in the source for a tag.
+    fn comments(&self, span: Range<usize>) -> Vec<&str> {
+        let source = &self.source[span];
+        lexer::lex(source)
+            .filter_map(|token| match token {
+                Token::Parameters(Parameters::LineComment, span) => {
+                    Some(source[span].trim_end())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Write source that is not part of a node up to a byte offset.
+    fn source_until(&mut self, end: usize) {
+        if end > self.offset {
+            let source = self.source;
+            self.text(&source[self.offset..end]);
+            self.offset = end;
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let indent = match self.options.indent {
+            Some(indent) if !self.indents.is_empty() => indent,
+            _ => {
+                self.out.push_str(text);
+                return;
+            }
+        };
+
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                self.out.push('\n');
+                self.pending = true;
+            }
+            if !self.pending {
+                self.out.push_str(line);
+                continue;
+            }
+            // Blank lines are not indented and when text ends at the
+            // start of a line the indentation is written before the
+            // next tag
+            let line = line.trim_start_matches([' ', '\t']);
+            if !line.is_empty() {
+                self.pending = false;
+                self.out.push_str(self.indents.last().unwrap());
+                self.out.push_str(&" ".repeat(indent));
+                self.out.push_str(line);
+            }
+        }
+    }
+
+    /// Write pending indentation before a tag at the start of a line;
+    /// close tags are aligned with the open tag.
+    fn line_start(&mut self, close: bool) {
+        if !self.pending {
+            return;
+        }
+        self.pending = false;
+        if let (Some(indent), Some(base)) =
+            (self.options.indent, self.indents.last())
+        {
+            self.out.push_str(base);
+            if !close {
+                self.out.push_str(&" ".repeat(indent));
+            }
+        }
+    }
+
+    /// Leading whitespace of the current line.
+    fn line_indent(&self) -> String {
+        let start = self.out.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        self.out[start..]
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect()
+    }
+}
+
+/// Get the name in the close tag for a block.
+fn close_name<'source>(block: &Block<'source>) -> &'source str {
+    block.close().trim_matches(|c: char| {
+        c == '{' || c == '}' || c == '~' || c == '/' || c.is_whitespace()
+    })
+}
+
+fn call_content(call: &Call<'_>) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if !call.is_empty() {
+        parts.push(match call.target() {
+            CallTarget::Path(ref path) => path_content(path),
+            CallTarget::SubExpr(ref sub) => {
+                format!("({})", call_content(sub))
+            }
+        });
+    }
+    for arg in call.arguments() {
+        parts.push(value_content(arg));
+    }
+    for (key, value) in call.parameters() {
+        parts.push(format!("{}={}", key, value_content(value)));
+    }
+    if !call.block_params().is_empty() {
+        parts.push(format!("as |{}|", call.block_params().join(" ")));
+    }
+    parts.join(" ")
+}
+
+fn value_content(value: &ParameterValue<'_>) -> String {
+    match value {
+        ParameterValue::Path(ref path) => path_content(path),
+        ParameterValue::Json { .. } => value.as_str().to_string(),
+        ParameterValue::SubExpr(ref call) => {
+            format!("({})", call_content(call))
+        }
+    }
+}
+
+fn path_content(path: &Path<'_>) -> String {
+    let mut result = String::new();
+    if path.absolute() {
+        result.push('/');
+    }
+    result.push_str(&"../".repeat(path.parents() as usize));
+    let mut delimiter = false;
+    for component in path.components() {
+        if delimiter {
+            result.push('.');
+        }
+        match component.kind() {
+            ComponentType::RawIdentifier(RawIdType::Single) => {
+                result.push_str(&format!("'{}'", component.as_str()))
+            }
+            ComponentType::RawIdentifier(RawIdType::Double) => {
+                result.push_str(&format!("\"{}\"", component.as_str()))
+            }
+            ComponentType::RawIdentifier(RawIdType::Array) => {
+                result.push_str(&format!("[{}]", component.as_str()))
+            }
+            _ => result.push_str(component.as_str()),
+        }
+        delimiter = !component.is_explicit_dot_slash();
+    }
+    result
+}
@@ -15,23 +15,42 @@ struct EscapeFlags {
     newline: bool,
     pipe: bool,
     bracket: bool,
+    open_bracket: bool,
+    backslash: bool,
 }
 
 impl EscapeFlags {
     fn has_escape_sequences(&self) -> bool {
-        self.newline || self.pipe || self.bracket
+        self.newline
+            || self.pipe
+            || self.bracket
+            || self.open_bracket
+            || self.backslash
     }
 
+    // Escape sequences are replaced in a single pass so that
+    // an escaped backslash is not treated as the start of
+    // another escape sequence.
     fn into_owned<'a>(&self, value: &'a str) -> String {
-        let mut val = value.to_string();
-        if self.newline {
-            val = val.replace("\\n", "\n");
-        }
-        if self.pipe {
-            val = val.replace("\\|", "|");
-        }
-        if self.bracket {
-            val = val.replace("\\]", "]");
+        let mut val = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                val.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') if self.newline => val.push('\n'),
+                Some('|') if self.pipe => val.push('|'),
+                Some(']') if self.bracket => val.push(']'),
+                Some('[') if self.open_bracket => val.push('['),
+                Some('\\') if self.backslash => val.push('\\'),
+                Some(next) => {
+                    val.push(c);
+                    val.push(next);
+                }
+                None => val.push(c),
+            }
         }
         val
     }
@@ -133,6 +152,12 @@ fn value<'source>(
                     lexer::Link::Escaped => {
                         flags.bracket = true;
                     }
+                    lexer::Link::EscapedOpenBracket => {
+                        flags.open_bracket = true;
+                    }
+                    lexer::Link::EscapedBackslash => {
+                        flags.backslash = true;
+                    }
                     lexer::Link::End => {
                         assign_if_escaped(
                             source,
@@ -214,6 +239,12 @@ fn href<'source>(
                     lexer::Link::Escaped => {
                         flags.bracket = true;
                     }
+                    lexer::Link::EscapedOpenBracket => {
+                        flags.open_bracket = true;
+                    }
+                    lexer::Link::EscapedBackslash => {
+                        flags.backslash = true;
+                    }
                     lexer::Link::End => {
                         if flags.has_escape_sequences() {
                             let value = flags.into_owned(
@@ -6,21 +6,29 @@ use crate::{
         ast::{Block, CallTarget, Document, Element, Lines, Node, Text},
         call::CallParseContext,
     },
-    SyntaxResult,
+    trim, SyntaxResult,
 };
 use std::ops::Range;
 
 /// Default file name.
 pub(crate) const UNKNOWN: &str = "unknown";
 
+/// Byte order mark.
+const BOM: char = '\u{FEFF}';
+
 pub mod ast;
 mod block;
 mod call;
+pub(crate) mod delimiter;
+pub mod format;
 pub mod iter;
 mod link;
 pub(crate) mod path;
 mod string;
 
+pub use delimiter::Delimiters;
+pub use format::FormatOptions;
+
 /// Set the file name used in error messages.
 ///
 /// It is also possible to set the line and byte offsets if your template
@@ -61,6 +69,19 @@ impl Default for ParserOptions {
     }
 }
 
+/// Get a lexer for template source that skips a leading
+/// byte order mark unless it should be preserved.
+///
+/// The byte order mark remains in the source so that
+/// byte offsets refer to the original source.
+fn lex_source(source: &str, preserve_bom: bool) -> Lexer<'_> {
+    let mut lexer = lex(source);
+    if !preserve_bom && source.starts_with(BOM) {
+        lexer.bump(BOM.len_utf8());
+    }
+    lexer
+}
+
 #[derive(Debug)]
 pub(crate) struct ParseState {
     file_name: String,
@@ -138,8 +159,11 @@ impl<'source> Parser<'source> {
     /// Create a new Parser for the given source template.
     ///
     /// This will prepare a lexer and initial state for the iterator.
+    ///
+    /// A byte order mark at the start of the source is skipped;
+    /// see [set_preserve_bom()](Parser#method.set_preserve_bom).
     pub fn new(source: &'source str, options: ParserOptions) -> Self {
-        let lexer = lex(source);
+        let lexer = lex_source(source, false);
         let state = ParseState::from(&options);
         Self {
             source,
@@ -160,17 +184,57 @@ impl<'source> Parser<'source> {
         self.errors = Some(errors);
     }
 
+    /// Set whether a byte order mark at the start of the source is
+    /// treated as template text.
+    ///
+    /// Must be called before the parser is iterated.
+    pub fn set_preserve_bom(&mut self, preserve: bool) {
+        self.lexer = lex_source(self.source, preserve);
+    }
+
     /// Parse the entire document into a node tree.
     ///
     /// This iterates the parser until completion and adds
     /// each node to a `Document` node which is returned.
+    ///
+    /// Lines that only contain a block tag, partial or comment
+    /// are removed from the text of the returned document; nodes
+    /// yielded by the iterator are not modified.
     pub fn parse(&mut self) -> SyntaxResult<Node<'source>> {
         let mut doc = Document(&self.source, vec![]);
         for node in self {
             let node = node?;
             doc.nodes_mut().push(node);
         }
-        Ok(Node::Document(doc))
+        let mut node = Node::Document(doc);
+        trim::standalone(&mut node);
+        Ok(node)
+    }
+
+    /// Parse nodes until the first syntax error.
+    ///
+    /// Returns the nodes that were parsed successfully before the
+    /// error along with the error and the byte offset where parsing
+    /// stopped; when there are no errors all the nodes in the
+    /// document are returned.
+    ///
+    /// Like the iterator the returned nodes are not modified to
+    /// remove standalone lines.
+    pub fn parse_partial(
+        &mut self,
+    ) -> (Vec<Node<'source>>, Option<(SyntaxError, usize)>) {
+        let mut nodes = Vec::new();
+        while let Some(t) = self.token() {
+            match self.advance(t) {
+                Ok(Some(node)) => nodes.push(node),
+                Ok(None) => break,
+                Err(e) => {
+                    let byte = *self.state.byte();
+                    return (nodes, Some((e, byte)));
+                }
+            }
+        }
+        (nodes, None)
     }
 
     /// Yield the next token accounting for text normalization which
@@ -243,13 +307,19 @@ impl<'source> Parser<'source> {
                     .map(Some);
                 }
                 lexer::Block::StartComment => {
-                    return block::comment(
+                    let (node, note) = block::comment(
                         self.source,
                         &mut self.lexer,
                         &mut self.state,
                         span,
-                    )
-                    .map(Some);
+                    )?;
+                    // Suspicious comments are only reported when linting
+                    if let (Some(note), Some(errors)) =
+                        (note, self.errors.as_mut())
+                    {
+                        errors.push(Error::from(note));
+                    }
+                    return Ok(Some(node));
                 }
                 lexer::Block::StartBlockScope => {
                     let block = block::scope(
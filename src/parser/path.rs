@@ -69,6 +69,24 @@ fn to_component<'source>(
     Ok(Component::new(source, kind, span, value))
 }
 
+/// Split a number token into the spans of integer array indices.
+fn array_indices(
+    source: &str,
+    span: &Range<usize>,
+) -> Option<Vec<Range<usize>>> {
+    let mut indices = Vec::new();
+    let mut start = span.start;
+    for part in source[span.clone()].split('.') {
+        let digits = part.strip_prefix('-').unwrap_or(part);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        indices.push(start..start + part.len());
+        start += part.len() + 1;
+    }
+    Some(indices)
+}
+
 fn parents<'source>(
     _state: &mut ParseState,
     lexer: &mut Lexer<'source>,
@@ -113,6 +131,24 @@ pub(crate) fn components<'source>(
                     return Ok(Some(Token::Parameters(lex, span)));
                 }
 
+                // Numbers after a delimiter are array indices, the lexer
+                // reads `1.2` as a single number so split on the period
+                if lex == Parameters::Number && !wants_delimiter {
+                    if let Some(indices) = array_indices(source, &span) {
+                        for index in indices {
+                            path.add_component(Component::new(
+                                source,
+                                ComponentType::Identifier,
+                                index,
+                                None,
+                            ));
+                        }
+                        path.span_mut().end = span.end;
+                        wants_delimiter = true;
+                        continue;
+                    }
+                }
+
                 if is_path_component(&lex) {
                     path.span_mut().end = span.end;
 
@@ -1,21 +1,66 @@
 //! Primary entry point for compiling and rendering templates.
 use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
 
 #[cfg(feature = "fs")]
 use std::ffi::OsStr;
 #[cfg(feature = "fs")]
 use std::path::Path;
 
+#[cfg(feature = "log-helper")]
+use crate::helper::log::LogSinkFn;
+
 use crate::{
+    error::RenderError,
     escape::{self, EscapeFn},
-    helper::{HandlerRegistry, HelperRegistry},
-    output::{Output, StringOutput},
-    parser::{Parser, ParserOptions},
-    render::CallSite,
+    helper::{HandlerRegistry, Helper, HelperRegistry},
+    output::{ChunkOutput, FmtOutput, NullOutput, Output, StringOutput},
+    parser::{
+        ast::{Call, CallTarget, Node, ParameterValue, Slice},
+        Delimiters, Parser, ParserOptions,
+    },
+    render::{CallSite, Render, SourceMap, STACK_MAX},
     template::{Template, Templates},
     Error, Result,
 };
 
+const EVAL: &str = "eval";
+
+/// Get the call for a document that contains a single statement.
+///
+/// Text nodes that only contain whitespace are ignored.
+fn expression<'a>(node: &'a Node<'a>) -> Option<&'a Call<'a>> {
+    let nodes = node
+        .into_iter()
+        .filter(|n| match n {
+            Node::Text(ref text) => !text.as_str().trim().is_empty(),
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+    match nodes[..] {
+        [Node::Statement(ref call)] if !call.is_partial() => Some(call),
+        _ => None,
+    }
+}
+
+/// Representation of non-finite floating point numbers.
+///
+/// JSON cannot represent `NaN` or infinity so helpers that compute
+/// floating point numbers use this representation when a result is
+/// not finite; see [Render::float()](crate::render::Render#method.float).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum NonFinite {
+    /// Render non-finite numbers as the empty string.
+    #[default]
+    Empty,
+    /// Render non-finite numbers as `NaN`, `Infinity` or `-Infinity`.
+    Name,
+    /// Render non-finite numbers as a custom token.
+    Token(String),
+}
+
 /// Registry is the entry point for compiling and rendering templates.
 ///
 /// A template name is always required for error messages.
@@ -24,7 +69,20 @@ pub struct Registry<'reg> {
     handlers: HandlerRegistry<'reg>,
     templates: Templates,
     escape: EscapeFn,
+    escape_extensions: HashMap<String, EscapeFn>,
     strict: bool,
+    render_link_labels: bool,
+    delimiters: Option<Delimiters>,
+    render_budget: Option<u64>,
+    max_iterations: Option<usize>,
+    max_stack_depth: usize,
+    flush_statements: bool,
+    preserve_bom: bool,
+    relative_partials: bool,
+    strip_raw_escape: bool,
+    non_finite: NonFinite,
+    #[cfg(feature = "log-helper")]
+    log_sink: Option<LogSinkFn<'reg>>,
 }
 
 impl<'reg> Registry<'reg> {
@@ -35,7 +93,27 @@ impl<'reg> Registry<'reg> {
             handlers: Default::default(),
             templates: Default::default(),
             escape: Box::new(escape::html),
+            escape_extensions: HashMap::new(),
             strict: false,
+            render_link_labels: false,
+            delimiters: None,
+            render_budget: None,
+            max_iterations: None,
+            max_stack_depth: STACK_MAX,
+            flush_statements: false,
+            preserve_bom: false,
+            relative_partials: false,
+            strip_raw_escape: true,
+            non_finite: Default::default(),
+            #[cfg(feature = "log-helper")]
+            log_sink: None,
+        }
+    }
+
+    /// Create a builder to configure a new registry.
+    pub fn builder() -> RegistryBuilder<'reg> {
+        RegistryBuilder {
+            registry: Registry::new(),
         }
     }
 
@@ -49,6 +127,157 @@ impl<'reg> Registry<'reg> {
         self.strict
     }
 
+    /// Set whether link labels and titles are rendered as templates.
+    ///
+    /// When enabled the label and title of each link are parsed and
+    /// rendered using the current scope before they are passed to the
+    /// link helper so they may contain variables; the rendered values
+    /// are not escaped as the link helper is expected to escape them.
+    pub fn set_render_link_labels(&mut self, render: bool) {
+        self.render_link_labels = render
+    }
+
+    /// Get whether link labels and titles are rendered as templates.
+    pub fn render_link_labels(&self) -> bool {
+        self.render_link_labels
+    }
+
+    /// Set the maximum number of nodes that may be rendered.
+    ///
+    /// Each render counts the nodes rendered including nodes rendered
+    /// by helpers and partials and returns an error when the budget
+    /// is exhausted; use this to limit the cost of rendering
+    /// untrusted templates or data. The default is `None` which
+    /// does not impose a limit.
+    pub fn set_render_budget(&mut self, budget: Option<u64>) {
+        self.render_budget = budget;
+    }
+
+    /// Get the render budget.
+    pub fn render_budget(&self) -> Option<u64> {
+        self.render_budget
+    }
+
+    /// Set the maximum number of iterations for a single expansion.
+    ///
+    /// The `each`, `range`, `repeat` and `chunk` helpers return an
+    /// error when an expansion would exceed this number of iterations
+    /// rather than rendering it; use this to guard against expensive
+    /// expansions in untrusted templates, for example,
+    /// `{{#each (range 0 999999999)}}`. The default is `None` which
+    /// does not impose a limit.
+    pub fn set_max_iterations(&mut self, limit: Option<usize>) {
+        self.max_iterations = limit;
+    }
+
+    /// Get the maximum number of iterations for a single expansion.
+    pub fn max_iterations(&self) -> Option<usize> {
+        self.max_iterations
+    }
+
+    /// Set the maximum depth of recursive calls to the same helper.
+    ///
+    /// When a helper is invoked recursively more than this number
+    /// of times a helper cycle error is returned. The default is 32.
+    pub fn set_max_stack_depth(&mut self, depth: usize) {
+        self.max_stack_depth = depth;
+    }
+
+    /// Get the maximum depth of recursive calls to the same helper.
+    pub fn max_stack_depth(&self) -> usize {
+        self.max_stack_depth
+    }
+
+    /// Set whether the output is flushed after each top-level statement.
+    ///
+    /// The output is always flushed when a render completes; enable
+    /// this to also flush after each statement and block in the
+    /// template so that output written to a buffered writer is
+    /// delivered incrementally.
+    pub fn set_flush_statements(&mut self, flush: bool) {
+        self.flush_statements = flush;
+    }
+
+    /// Get whether the output is flushed after each top-level statement.
+    pub fn flush_statements(&self) -> bool {
+        self.flush_statements
+    }
+
+    /// Set whether a byte order mark is kept when compiling templates.
+    ///
+    /// By default a byte order mark at the start of the template
+    /// source is skipped so that it is not written to the output;
+    /// enable this to treat it as part of the template text.
+    pub fn set_preserve_bom(&mut self, preserve: bool) {
+        self.preserve_bom = preserve;
+    }
+
+    /// Get whether a byte order mark is kept when compiling templates.
+    pub fn preserve_bom(&self) -> bool {
+        self.preserve_bom
+    }
+
+    /// Set whether partials are resolved relative to the directory
+    /// of the partial or template being rendered.
+    ///
+    /// When enabled a template named `pages/home` that includes
+    /// `{{> header}}` renders the `pages/header` template if it exists
+    /// and falls back to the `header` template otherwise; within
+    /// `pages/header` itself `{{> header}}` renders the `header`
+    /// template. Inline partials always take precedence.
+    pub fn set_relative_partials(&mut self, relative: bool) {
+        self.relative_partials = relative;
+    }
+
+    /// Get whether partials are resolved relative to the directory
+    /// of the partial or template being rendered.
+    pub fn relative_partials(&self) -> bool {
+        self.relative_partials
+    }
+
+    /// Set whether the backslash is removed from raw statements.
+    ///
+    /// A statement preceeded by a backslash, for example `\{{title}}`,
+    /// is rendered as text; by default the backslash is removed so the
+    /// output is `{{title}}`. Disable this to keep the backslash.
+    pub fn set_strip_raw_escape(&mut self, strip: bool) {
+        self.strip_raw_escape = strip;
+    }
+
+    /// Get whether the backslash is removed from raw statements.
+    pub fn strip_raw_escape(&self) -> bool {
+        self.strip_raw_escape
+    }
+
+    /// Set the representation of non-finite numbers.
+    ///
+    /// Used by helpers that compute floating point numbers when the
+    /// result is `NaN` or infinity, the default is the empty string;
+    /// see [Render::float()](crate::render::Render#method.float).
+    pub fn set_non_finite_repr(&mut self, repr: NonFinite) {
+        self.non_finite = repr;
+    }
+
+    /// Get the representation of non-finite numbers.
+    pub fn non_finite_repr(&self) -> &NonFinite {
+        &self.non_finite
+    }
+
+    /// Set custom delimiters used when compiling templates.
+    ///
+    /// Templates compiled after the delimiters are set use
+    /// `open` and `close` instead of `{{` and `}}`; both delimiters
+    /// must be two bytes. See [Delimiters](crate::parser::Delimiters).
+    pub fn set_delimiters(&mut self, open: &str, close: &str) -> Result<()> {
+        self.delimiters = Some(Delimiters::new(open, close)?);
+        Ok(())
+    }
+
+    /// Get the custom delimiters.
+    pub fn delimiters(&self) -> Option<&Delimiters> {
+        self.delimiters.as_ref()
+    }
+
     /// Set the escape function for rendering.
     pub fn set_escape(&mut self, escape: EscapeFn) {
         self.escape = escape;
@@ -59,6 +288,68 @@ impl<'reg> Registry<'reg> {
         &self.escape
     }
 
+    /// Set escape functions keyed by file extension.
+    ///
+    /// Templates loaded from files using [add()](Registry#method.add),
+    /// [load()](Registry#method.load) or [read_dir()](Registry#method.read_dir)
+    /// after the escape functions are set record the extension of the
+    /// file when it has an escape function and are rendered using
+    /// that escape function, for example, to escape `html` templates
+    /// but not `txt` templates:
+    ///
+    /// ```ignore
+    /// let mut escapes: HashMap<String, EscapeFn> = HashMap::new();
+    /// escapes.insert("html".to_string(), Box::new(escape::html));
+    /// escapes.insert("txt".to_string(), Box::new(escape::noop));
+    /// registry.set_escape_by_extension(escapes);
+    /// ```
+    ///
+    /// Templates compiled from strings use the escape function
+    /// for the registry.
+    pub fn set_escape_by_extension(
+        &mut self,
+        escapes: HashMap<String, EscapeFn>,
+    ) {
+        self.escape_extensions = escapes;
+    }
+
+    /// The escape function to use for rendering a template.
+    pub fn escape_for(&self, template: &Template) -> &EscapeFn {
+        template
+            .extension()
+            .and_then(|ext| self.escape_extensions.get(ext))
+            .unwrap_or(&self.escape)
+    }
+
+    /// Get the extension for a file when an escape function is
+    /// registered for the extension.
+    #[cfg(feature = "fs")]
+    fn escape_extension(&self, file: &Path) -> Option<String> {
+        file.extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+            .filter(|ext| self.escape_extensions.contains_key(ext))
+    }
+
+    /// Set a function that captures messages from the `log` helper.
+    ///
+    /// When a log sink is set messages are sent to the sink
+    /// instead of the `log` facade; useful to intercept template
+    /// log messages in applications and tests.
+    ///
+    /// Requires the `log-helper` feature.
+    #[cfg(feature = "log-helper")]
+    pub fn set_log_sink(&mut self, sink: LogSinkFn<'reg>) {
+        self.log_sink = Some(sink);
+    }
+
+    /// The function that captures messages from the `log` helper.
+    ///
+    /// Requires the `log-helper` feature.
+    #[cfg(feature = "log-helper")]
+    pub fn log_sink(&self) -> Option<&LogSinkFn<'reg>> {
+        self.log_sink.as_ref()
+    }
+
     /// Helper registry.
     pub fn helpers(&self) -> &HelperRegistry<'reg> {
         &self.helpers
@@ -69,6 +360,38 @@ impl<'reg> Registry<'reg> {
         &mut self.helpers
     }
 
+    /// Iterate the names of the registered helpers.
+    pub fn helper_names(&self) -> impl Iterator<Item = &str> {
+        self.helpers.names()
+    }
+
+    /// Remove a helper so that templates in this registry cannot call it.
+    ///
+    /// Returns `true` if the helper was registered; builtin helpers
+    /// can be restored using [enable_builtin()](Registry#method.enable_builtin).
+    pub fn disable_helper(&mut self, name: &str) -> bool {
+        self.helpers.remove(name).is_some()
+    }
+
+    /// Remove a helper and return it.
+    ///
+    /// Use this to wrap an existing helper, for example, to trace
+    /// calls, and then insert the wrapper using the same name.
+    pub fn take_helper(
+        &mut self,
+        name: &str,
+    ) -> Option<Box<dyn Helper + 'reg>> {
+        self.helpers.remove(name)
+    }
+
+    /// Restore a builtin helper that was disabled or replaced.
+    ///
+    /// Returns `false` when there is no builtin helper with the given
+    /// name or the helper was disabled by the feature flags.
+    pub fn enable_builtin(&mut self, name: &str) -> bool {
+        self.helpers.enable_builtin(name)
+    }
+
     /// Event handler registry.
     pub fn handlers(&self) -> &HandlerRegistry<'reg> {
         &self.handlers
@@ -134,9 +457,11 @@ impl<'reg> Registry<'reg> {
             .into_owned()
             .to_string();
 
+        let extension = self.escape_extension(file.as_ref());
         let (_, content) = self.read(file)?;
-        let template =
+        let mut template =
             self.compile(content, ParserOptions::new(file_name, 0, 0))?;
+        template.set_extension(extension);
         self.templates.insert(name, template);
         Ok(())
     }
@@ -152,9 +477,11 @@ impl<'reg> Registry<'reg> {
             .into_owned()
             .to_string();
 
+        let extension = self.escape_extension(file.as_ref());
         let (name, content) = self.read(file)?;
-        let template =
+        let mut template =
             self.compile(content, ParserOptions::new(file_name, 0, 0))?;
+        template.set_extension(extension);
         self.templates.insert(name, template);
         Ok(())
     }
@@ -171,6 +498,25 @@ impl<'reg> Registry<'reg> {
         &mut self,
         file: P,
         extension: &str,
+    ) -> Result<()> {
+        self.read_dir_prefixed(file, extension, "")
+    }
+
+    /// Load all the files in a target directory that match the
+    /// given extension and prefix the generated names.
+    ///
+    /// The generated name is the prefix followed by the file stem so
+    /// templates loaded from different directories do not collide;
+    /// for example, `button.hbs` loaded with the prefix `ui/` is
+    /// named `ui/button`.
+    ///
+    /// Requires the `fs` feature.
+    #[cfg(feature = "fs")]
+    pub fn read_dir_prefixed<P: AsRef<Path>>(
+        &mut self,
+        file: P,
+        extension: &str,
+        prefix: &str,
     ) -> Result<()> {
         let ext = OsStr::new(extension);
         for entry in std::fs::read_dir(file.as_ref())? {
@@ -184,17 +530,18 @@ impl<'reg> Registry<'reg> {
                             .into_owned()
                             .to_string();
 
-                        let name = path
-                            .file_stem()
-                            .unwrap()
-                            .to_string_lossy()
-                            .to_owned()
-                            .to_string();
+                        let name = format!(
+                            "{}{}",
+                            prefix,
+                            path.file_stem().unwrap().to_string_lossy()
+                        );
+                        let extension = self.escape_extension(&path);
                         let (_, content) = self.read(path)?;
-                        let template = self.compile(
+                        let mut template = self.compile(
                             content,
                             ParserOptions::new(file_name, 0, 0),
                         )?;
+                        template.set_extension(extension);
                         self.templates.insert(name, template);
                     }
                 }
@@ -226,13 +573,23 @@ impl<'reg> Registry<'reg> {
     where
         S: AsRef<str>,
     {
-        Ok(Template::compile(template.as_ref().to_owned(), options)?)
+        let source = template.as_ref().to_owned();
+        Ok(Template::compile_with(
+            source,
+            options,
+            self.delimiters.as_ref(),
+            self.preserve_bom,
+        )?)
     }
 
     /// Compile a string to a template using the given name.
     ///
     /// This is a convenience function for calling [compile()](Registry#method.compile)
     /// using parser options with the given name.
+    ///
+    /// The template owns the source so it can be stored and rendered
+    /// many times with [render_template()](Registry#method.render_template)
+    /// without inserting it into the registry.
     pub fn parse<'a, S>(&self, name: &str, template: S) -> Result<Template>
     where
         S: AsRef<str>,
@@ -240,21 +597,253 @@ impl<'reg> Registry<'reg> {
         self.compile(template, ParserOptions::new(name.to_string(), 0, 0))
     }
 
+    /// Compile a template fragment extracted from a larger document.
+    ///
+    /// The `line` and `byte` offsets locate the fragment in the host
+    /// document so that error messages refer to the original file
+    /// position; this is a convenience function for calling
+    /// [compile()](Registry#method.compile) with parser options.
+    pub fn compile_fragment<S>(
+        &self,
+        source: S,
+        file_name: &str,
+        line: usize,
+        byte: usize,
+    ) -> Result<Template>
+    where
+        S: AsRef<str>,
+    {
+        self.compile(
+            source,
+            ParserOptions::new(file_name.to_string(), line, byte),
+        )
+    }
+
     /// Lint a template.
     pub fn lint<S>(&self, name: &str, template: S) -> Result<Vec<Error>>
     where
         S: AsRef<str>,
     {
         let mut errors: Vec<Error> = Vec::new();
+        let canonical = self
+            .delimiters
+            .as_ref()
+            .map(|d| d.rewrite(template.as_ref()));
         let mut parser = Parser::new(
-            template.as_ref(),
+            canonical.as_deref().unwrap_or(template.as_ref()),
             ParserOptions::new(name.to_string(), 0, 0),
         );
+        parser.set_preserve_bom(self.preserve_bom);
         parser.set_errors(&mut errors);
         for _ in parser {}
         Ok(errors)
     }
 
+    /// Lint a template and report calls to unknown helpers and partials.
+    ///
+    /// Syntax errors are returned in the same way as
+    /// [lint()](Registry#method.lint); when the template parses the
+    /// tree is checked against the registered helpers and templates.
+    ///
+    /// A simple path call with arguments or hash parameters is an
+    /// error when no helper with that name is registered and there
+    /// is no `helper_missing` handler; calls without arguments are
+    /// assumed to be variables. Partials are errors when there is
+    /// no registered template or inline partial with that name.
+    ///
+    /// Local helpers registered by other helpers at render time
+    /// are not known to the linter.
+    pub fn lint_strict<S>(&self, name: &str, template: S) -> Result<Vec<Error>>
+    where
+        S: AsRef<str>,
+    {
+        let errors = self.lint(name, template.as_ref())?;
+        if !errors.is_empty() {
+            return Ok(errors);
+        }
+
+        let template = self.compile(
+            template.as_ref(),
+            ParserOptions::new(name.to_string(), 0, 0),
+        )?;
+
+        let mut inline: Vec<String> = Vec::new();
+        template.walk(|node| {
+            if let Node::Block(ref block) = node {
+                let call = block.call();
+                if call.is_decorator() {
+                    if let Some(ParameterValue::Json {
+                        value: Value::String(ref name),
+                        ..
+                    }) = call.arguments().first()
+                    {
+                        inline.push(name.to_string());
+                    }
+                }
+            }
+        });
+
+        // Partials that resolve relative to the template directory
+        if self.relative_partials {
+            if let Some((dir, _)) = name.rsplit_once('/') {
+                let prefix = format!("{}/", dir);
+                inline.extend(
+                    self.templates
+                        .keys()
+                        .filter_map(|k| k.strip_prefix(&prefix))
+                        .map(|k| k.to_string()),
+                );
+            }
+        }
+
+        let mut errors: Vec<Error> = Vec::new();
+        template.walk(|node| match node {
+            Node::Statement(ref call) => {
+                self.lint_call(call, false, &inline, &mut errors)
+            }
+            Node::Block(ref block) => self.lint_call(
+                block.call(),
+                block.is_raw(),
+                &inline,
+                &mut errors,
+            ),
+            _ => {}
+        });
+        Ok(errors)
+    }
+
+    /// Lint a template against sample data.
+    ///
+    /// Performs the same checks as
+    /// [lint_strict()](Registry#method.lint_strict) and also reports
+    /// simple path statements without arguments, for example `{{name}}`,
+    /// where `name` is a registered helper and a field of the sample
+    /// data. Helpers take precedence so the variable is shadowed; these
+    /// diagnostics are informational and do not prevent rendering.
+    pub fn lint_data<S>(
+        &self,
+        name: &str,
+        template: S,
+        data: &Value,
+    ) -> Result<Vec<Error>>
+    where
+        S: AsRef<str>,
+    {
+        let mut errors = self.lint_strict(name, template.as_ref())?;
+        let fields = match data {
+            Value::Object(ref map) => map,
+            _ => return Ok(errors),
+        };
+
+        let template = self.compile(
+            template.as_ref(),
+            ParserOptions::new(name.to_string(), 0, 0),
+        )?;
+        template.walk(|node| {
+            if let Node::Statement(ref call) = node {
+                if let CallTarget::Path(ref path) = call.target() {
+                    let name = path.as_str();
+                    if !call.is_partial()
+                        && path.is_simple()
+                        && call.arguments().is_empty()
+                        && call.parameters().is_empty()
+                        && self.helpers.contains(name)
+                        && fields.contains_key(name)
+                    {
+                        errors.push(Error::from(
+                            RenderError::HelperShadowsVariable(
+                                name.to_string(),
+                            ),
+                        ));
+                    }
+                }
+            }
+        });
+        Ok(errors)
+    }
+
+    fn lint_call(
+        &self,
+        call: &Call<'_>,
+        raw: bool,
+        inline: &[String],
+        errors: &mut Vec<Error>,
+    ) {
+        for arg in call.arguments() {
+            if let ParameterValue::SubExpr(ref sub) = arg {
+                self.lint_call(sub, false, inline, errors);
+            }
+        }
+        for value in call.parameters().values() {
+            if let ParameterValue::SubExpr(ref sub) = value {
+                self.lint_call(sub, false, inline, errors);
+            }
+        }
+
+        if call.is_decorator() || call.is_empty() {
+            return;
+        }
+
+        let path = match call.target() {
+            CallTarget::Path(ref path)
+                if path.is_simple()
+                    || (call.is_partial() && path.is_identifiers()) =>
+            {
+                path
+            }
+            CallTarget::SubExpr(ref sub) => {
+                return self.lint_call(sub, false, inline, errors)
+            }
+            _ => return,
+        };
+        let name = path.as_str();
+
+        if call.is_partial() {
+            if !self.templates.contains_key(name)
+                && !inline.iter().any(|n| n == name)
+            {
+                errors.push(Error::from(RenderError::PartialNotFound(
+                    name.to_string(),
+                )));
+            }
+        } else if !raw
+            && (!call.arguments().is_empty() || !call.parameters().is_empty())
+            && !self.helpers.contains(name)
+            && self.handlers.helper_missing.is_none()
+        {
+            errors.push(Error::from(RenderError::HelperNotFound(
+                name.to_string(),
+            )));
+        }
+    }
+
+    /// Evaluate an expression and return the resulting value.
+    ///
+    /// The expression is the content of a statement without the
+    /// braces, for example `foo.bar` or `(gt score 10)`; the value
+    /// is returned as-is rather than converted to a string. When
+    /// the expression does not yield a value `Value::Null` is returned.
+    pub fn eval<T>(&self, expr: &str, data: &T) -> Result<Value>
+    where
+        T: Serialize,
+    {
+        let source = format!("{{{{{}}}}}", expr);
+        let template =
+            self.compile(&source, ParserOptions::new(EVAL.to_string(), 0, 0))?;
+        let call = expression(template.node()).ok_or_else(|| {
+            Error::from(RenderError::InvalidExpression(expr.to_string()))
+        })?;
+        let mut writer = StringOutput::new();
+        let mut rc = Render::new(
+            self,
+            EVAL,
+            data,
+            Box::new(&mut writer),
+            Default::default(),
+        )?;
+        Ok(rc.call(call)?.unwrap_or(Value::Null))
+    }
+
     /// Render a template without registering it and return
     /// the result as a string.
     ///
@@ -392,6 +981,195 @@ impl<'reg> Registry<'reg> {
         Ok(writer.into())
     }
 
+    /// Render a named template using borrowed data and buffer
+    /// the result to a string.
+    ///
+    /// Use this when the data is already a `Value` to avoid the
+    /// conversion and clone performed by [render()](Registry#method.render).
+    ///
+    /// The named template must exist in the templates collection.
+    pub fn render_value_ref(&self, name: &str, data: &Value) -> Result<String> {
+        let tpl = self
+            .templates
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let mut writer = StringOutput::new();
+        tpl.render_value(self, name, data, &mut writer, Default::default())?;
+        Ok(writer.into())
+    }
+
+    /// Render a named template to a formatting writer.
+    ///
+    /// The output is appended to the writer so this can be used
+    /// to render into an existing `String` buffer or a `Formatter`.
+    ///
+    /// The named template must exist in the templates collection.
+    pub fn render_to_fmt<W, T>(
+        &self,
+        name: &str,
+        data: &T,
+        out: &mut W,
+    ) -> Result<()>
+    where
+        W: fmt::Write,
+        T: Serialize,
+    {
+        let mut writer = FmtOutput::new(out);
+        self.render_to_write(name, data, &mut writer)
+    }
+
+    /// Render a named template and pass the output to a callback
+    /// in chunks as it is rendered.
+    ///
+    /// The output is split after each top-level statement or block
+    /// and any text that precedes it so the first chunk can be
+    /// delivered before the rest of the template has rendered.
+    /// Returning an error from the callback stops rendering.
+    ///
+    /// To produce an iterator or stream send the chunks over a
+    /// channel and render on another thread.
+    ///
+    /// The named template must exist in the templates collection.
+    pub fn render_chunks<T, F>(
+        &self,
+        name: &str,
+        data: &T,
+        callback: F,
+    ) -> Result<()>
+    where
+        T: Serialize,
+        F: FnMut(String) -> std::io::Result<()>,
+    {
+        let tpl = self
+            .templates
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let mut writer = ChunkOutput::new(callback);
+        let mut rc = Render::new(
+            self,
+            name,
+            data,
+            Box::new(&mut writer),
+            Default::default(),
+        )?;
+        rc.set_flush_statements(true);
+        rc.push_template_escape(tpl);
+        rc.render(tpl.node())?;
+        Ok(())
+    }
+
+    /// Render a named template and return the result as a string
+    /// with a source map.
+    ///
+    /// Each source map entry pairs a byte range in the output with
+    /// the byte range of the node in the template source that wrote
+    /// the output; adjacent output from the same node is merged into
+    /// a single entry. Output from partials is mapped to the partial
+    /// call in the named template.
+    ///
+    /// The named template must exist in the templates collection.
+    pub fn render_with_sourcemap<T>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<(String, SourceMap)>
+    where
+        T: Serialize,
+    {
+        let tpl = self
+            .templates
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let mut writer = StringOutput::new();
+        let mut rc = Render::new(
+            self,
+            name,
+            data,
+            Box::new(&mut writer),
+            Default::default(),
+        )?;
+        rc.set_source_map(tpl.node().source());
+        rc.push_template_escape(tpl);
+        rc.render(tpl.node())?;
+        let source_map = rc.take_source_map();
+        drop(rc);
+        Ok((writer.into(), source_map))
+    }
+
+    /// Render a named template with additional partials and
+    /// return the result as a string.
+    ///
+    /// The partials are only available for this render and take
+    /// precedence over registered templates with the same name; use
+    /// this when partial content varies for each render, for example,
+    /// a layout that is chosen per request.
+    ///
+    /// The named template must exist in the templates collection.
+    pub fn render_with_partials<'a, T>(
+        &'a self,
+        name: &'a str,
+        data: &T,
+        partials: &HashMap<String, &'a Node<'a>>,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let tpl = self
+            .templates
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let mut writer = StringOutput::new();
+        let mut rc = Render::new(
+            self,
+            name,
+            data,
+            Box::new(&mut writer),
+            Default::default(),
+        )?;
+        for (name, node) in partials {
+            rc.insert_partial(name.to_string(), node);
+        }
+        rc.push_template_escape(tpl);
+        rc.render(tpl.node())?;
+        drop(rc);
+        Ok(writer.into())
+    }
+
+    /// Render a named template that contains a single statement
+    /// and return the resulting value.
+    ///
+    /// Unlike `render()` the value is not converted to a string
+    /// so the type of the value is preserved; leading and trailing
+    /// whitespace in the template is ignored. If the template is not
+    /// a single statement an error is returned.
+    ///
+    /// The named template must exist in the templates collection.
+    pub fn render_single_value<T>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<Option<Value>>
+    where
+        T: Serialize,
+    {
+        let tpl = self
+            .templates
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let call = expression(tpl.node()).ok_or_else(|| {
+            Error::from(RenderError::SingleExpression(name.to_string()))
+        })?;
+        let mut writer = StringOutput::new();
+        let mut rc = Render::new(
+            self,
+            name,
+            data,
+            Box::new(&mut writer),
+            Default::default(),
+        )?;
+        Ok(rc.call(call)?)
+    }
+
     /// Render a compiled template without registering it and
     /// buffer the result to a string.
     pub fn render_template<'a, T>(
@@ -408,6 +1186,31 @@ impl<'reg> Registry<'reg> {
         Ok(writer.into())
     }
 
+    /// Render every registered template against sample data and
+    /// collect the render errors.
+    ///
+    /// The output is discarded; each failure is returned with the
+    /// name of the template, ordered by name. Use this as a smoke
+    /// test to find missing partials and helpers, cycles and strict
+    /// mode failures across a set of templates.
+    pub fn validate<T>(&self, sample: &T) -> Vec<(String, RenderError)>
+    where
+        T: Serialize,
+    {
+        let mut names: Vec<&String> = self.templates.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let mut writer = NullOutput::new();
+                self.templates[name]
+                    .render(self, name, sample, &mut writer, Default::default())
+                    .err()
+                    .map(|e| (name.to_string(), e))
+            })
+            .collect()
+    }
+
     /// Render a named template to a writer.
     ///
     /// The named template must exist in the templates collection.
@@ -429,3 +1232,84 @@ impl<'reg> Registry<'reg> {
         Ok(())
     }
 }
+
+/// Builder for configuring a registry.
+///
+/// ```ignore
+/// let registry = Registry::builder()
+///     .helper("upper", Box::new(Upper {}))
+///     .strict(true)
+///     .escape(Box::new(escape::noop))
+///     .build();
+/// ```
+pub struct RegistryBuilder<'reg> {
+    registry: Registry<'reg>,
+}
+
+impl<'reg> RegistryBuilder<'reg> {
+    /// Register a helper.
+    pub fn helper(
+        mut self,
+        name: &'reg str,
+        helper: Box<dyn Helper + 'reg>,
+    ) -> Self {
+        self.registry.helpers.insert(name, helper);
+        self
+    }
+
+    /// Set the helper invoked when a helper is missing.
+    pub fn helper_missing(mut self, helper: Box<dyn Helper + 'reg>) -> Self {
+        self.registry.handlers.helper_missing = Some(helper);
+        self
+    }
+
+    /// Set the helper invoked when a block helper is missing.
+    pub fn block_helper_missing(
+        mut self,
+        helper: Box<dyn Helper + 'reg>,
+    ) -> Self {
+        self.registry.handlers.block_helper_missing = Some(helper);
+        self
+    }
+
+    /// Set the helper invoked when a link is encountered.
+    pub fn link(mut self, helper: Box<dyn Helper + 'reg>) -> Self {
+        self.registry.handlers.link = Some(helper);
+        self
+    }
+
+    /// Set the strict mode.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.registry.set_strict(strict);
+        self
+    }
+
+    /// Set the escape function for rendering.
+    pub fn escape(mut self, escape: EscapeFn) -> Self {
+        self.registry.set_escape(escape);
+        self
+    }
+
+    /// Set the maximum depth of recursive calls to the same helper.
+    pub fn max_stack_depth(mut self, depth: usize) -> Self {
+        self.registry.set_max_stack_depth(depth);
+        self
+    }
+
+    /// Set the maximum number of nodes that may be rendered.
+    pub fn render_budget(mut self, budget: Option<u64>) -> Self {
+        self.registry.set_render_budget(budget);
+        self
+    }
+
+    /// Set the maximum number of iterations for a single expansion.
+    pub fn max_iterations(mut self, limit: Option<usize>) -> Self {
+        self.registry.set_max_iterations(limit);
+        self
+    }
+
+    /// Build the registry.
+    pub fn build(self) -> Registry<'reg> {
+        self.registry
+    }
+}
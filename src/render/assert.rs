@@ -49,17 +49,22 @@ impl From<&Value> for Type {
 
 /// Assert on the type of a value.
 ///
-/// The type of the value must be one of the given types.
+/// The assertion passes when the type of the value matches any
+/// of the given types. Previously every type had to match so an
+/// assertion with more than one type could never pass.
 ///
 /// If the type assertion fails the returned value contains a string
-/// of the current type that caused the failure.
+/// of the expected types separated by a pipe.
 pub fn assert(value: &Value, kinds: &[Type]) -> (bool, Option<String>) {
-    for kind in kinds {
-        if !assert_type(value, kind) {
-            return (false, Some(kind.to_string()));
-        }
+    if kinds.iter().any(|kind| assert_type(value, kind)) {
+        return (true, None);
     }
-    (true, None)
+    let expected = kinds
+        .iter()
+        .map(|kind| kind.to_string())
+        .collect::<Vec<_>>()
+        .join("|");
+    (false, Some(expected))
 }
 
 fn assert_type(value: &Value, kind: &Type) -> bool {
@@ -7,10 +7,22 @@ use crate::{
     error::HelperError,
     helper::HelperResult,
     json,
-    parser::ast::{Call, Node, Slice},
+    parser::ast::{Call, Lines, Node, Slice},
     render::assert::{assert, Type},
 };
 
+/// Create a helper error with a formatted message.
+///
+/// ```ignore
+/// return Err(helper_error!("Helper '{}' got bad input", ctx.name()));
+/// ```
+#[macro_export]
+macro_rules! helper_error {
+    ($($arg:tt)*) => {
+        $crate::error::HelperError::Message(format!($($arg)*))
+    };
+}
+
 /// Represents a value to use when a variable lookup fails.
 ///
 /// The underlying value is guaranteed to be `Value::String` and
@@ -23,6 +35,17 @@ pub enum MissingValue {
     Parameter(String, Value),
 }
 
+/// Indicates how a helper was invoked.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Invocation {
+    /// Invoked as a statement, eg: `{{helper}}`.
+    Statement,
+    /// Invoked as a block, eg: `{{#helper}}{{/helper}}`.
+    Block,
+    /// Invoked as a raw block, eg: `{{{{helper}}}}{{{{/helper}}}}`.
+    Raw,
+}
+
 /// Property represents a key/value pair.
 ///
 /// This is used so that `blockHelperMissing` handlers have access
@@ -44,6 +67,8 @@ pub struct Context<'call> {
     // TODO: use call to generate context specific errors!
     call: &'call Call<'call>,
     name: String,
+    template_name: String,
+    invocation: Invocation,
     arguments: Vec<Value>,
     parameters: Map<String, Value>,
     text: Option<&'call str>,
@@ -52,9 +77,12 @@ pub struct Context<'call> {
 }
 
 impl<'call> Context<'call> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         call: &'call Call<'call>,
         name: String,
+        template_name: String,
+        invocation: Invocation,
         arguments: Vec<Value>,
         parameters: Map<String, Value>,
         text: Option<&'call str>,
@@ -64,6 +92,8 @@ impl<'call> Context<'call> {
         Self {
             call,
             name,
+            template_name,
+            invocation,
             arguments,
             parameters,
             text,
@@ -77,16 +107,51 @@ impl<'call> Context<'call> {
         &self.name
     }
 
+    /// Get the name of the template that contains the call.
+    ///
+    /// When the call is inside a partial this is the name of the partial.
+    pub fn template_name(&self) -> &str {
+        &self.template_name
+    }
+
+    /// Get how the helper was invoked.
+    pub fn invocation(&self) -> Invocation {
+        self.invocation
+    }
+
+    /// Determine if the helper was invoked with a block template.
+    ///
+    /// This is also true for raw blocks.
+    pub fn is_block(&self) -> bool {
+        self.invocation != Invocation::Statement
+    }
+
+    /// Determine if the helper was invoked as a raw block.
+    ///
+    /// When true the raw content is available via [text()](Context#method.text).
+    pub fn is_raw(&self) -> bool {
+        self.invocation == Invocation::Raw
+    }
+
     /// Get the list of arguments.
     pub fn arguments(&self) -> &Vec<Value> {
         &self.arguments
     }
 
     /// Get the map of hash parameters.
+    ///
+    /// The map is ordered by key unless the `preserve_order` feature
+    /// is enabled; use [hash_keys()](Context#method.hash_keys) to visit
+    /// the parameters in template source order.
     pub fn parameters(&self) -> &Map<String, Value> {
         &self.parameters
     }
 
+    /// Iterate the names of the hash parameters in template source order.
+    pub fn hash_keys(&self) -> impl Iterator<Item = &str> {
+        self.call.parameters().keys().copied()
+    }
+
     /// Get an argument at an index.
     pub fn get(&self, index: usize) -> Option<&Value> {
         self.arguments.get(index)
@@ -170,6 +235,16 @@ impl<'call> Context<'call> {
         self.call.parameters().get(name).map(|v| v.as_str())
     }
 
+    /// Get the original source text for an argument at an index.
+    ///
+    /// The returned slice is the unparsed template source for the
+    /// argument so string literals include their surrounding quotes
+    /// and sub-expressions include the parentheses; useful for
+    /// generating error messages that refer to the template.
+    pub fn argument_source(&self, index: usize) -> Option<&'call str> {
+        self.call.arguments().get(index).map(|v| v.as_str())
+    }
+
     /// Get an argument at an index and assert that the value
     /// is one of the given types.
     ///
@@ -187,6 +262,26 @@ impl<'call> Context<'call> {
         Ok(value)
     }
 
+    /// Get all the arguments and assert that every value is
+    /// one of the given types.
+    ///
+    /// Use this for helpers that accept a variable number of
+    /// arguments of the same type; the error names the index of
+    /// the first argument that does not match.
+    pub fn try_get_all(&self, kinds: &[Type]) -> HelperResult<&[Value]> {
+        for (index, value) in self.arguments.iter().enumerate() {
+            if let (false, Some(expected)) = assert(value, kinds) {
+                return Err(HelperError::ArgumentType(
+                    self.name().to_string(),
+                    index,
+                    expected,
+                    Type::from(value).to_string(),
+                ));
+            }
+        }
+        Ok(&self.arguments)
+    }
+
     /// Get a hash parameter for the name and assert that the value
     /// is one of the given types.
     ///
@@ -256,19 +351,42 @@ impl<'call> Context<'call> {
         Ok(())
     }
 
+    /// Assert that the call has at least a minimum number of arguments.
+    ///
+    /// Use this for helpers that accept a variable number of arguments.
+    pub fn arity_min(&self, min: usize) -> HelperResult<()> {
+        if self.arguments.len() < min {
+            return Err(HelperError::ArityMin(self.name.clone(), min));
+        }
+        Ok(())
+    }
+
     /// Assert on the type of a value.
     pub fn assert(&self, value: &Value, kinds: &[Type]) -> HelperResult<()> {
         let (result, kind) = assert(value, kinds);
         if !result {
-            return Err(HelperError::TypeAssert(
+            return Err(HelperError::TypeAssertAt(
                 self.name().to_string(),
                 kind.unwrap(),
                 Type::from(value).to_string(),
+                self.template_name.clone(),
+                self.call.lines().start + 1,
             ));
         }
         Ok(())
     }
 
+    /// Fail with a generic message error.
+    ///
+    /// Helpers can use this to report a user-facing error:
+    ///
+    /// ```ignore
+    /// return ctx.fail("Expected a list of links");
+    /// ```
+    pub fn fail<T>(&self, msg: impl Into<String>) -> HelperResult<T> {
+        Err(HelperError::Message(msg.into()))
+    }
+
     /// Map an optional template to a result.
     ///
     /// If the template is `None` this will yield an error; use this
@@ -1,14 +1,18 @@
 //! Render a template to output using the data.
-use std::cell::RefCell;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fmt;
+use std::io::Write;
+use std::ops::Range;
 use std::rc::Rc;
 
 use serde::Serialize;
 use serde_json::{Map, Value};
 
 use crate::{
-    error::{HelperError, RenderError},
+    error::{HelperError, IoError, RenderError},
+    escape::{self, EscapeFn},
     helper::{Helper, HelperResult, LocalHelper},
     json,
     output::{Output, StringOutput},
@@ -17,7 +21,7 @@ use crate::{
             Block, Call, CallTarget, Lines, Link, Node, ParameterValue, Path,
             Slice,
         },
-        path,
+        path, ParserOptions,
     },
     template::Template,
     trim::{TrimHint, TrimState},
@@ -28,6 +32,8 @@ const PARTIAL_BLOCK: &str = "@partial-block";
 const HELPER_MISSING: &str = "helperMissing";
 const BLOCK_HELPER_MISSING: &str = "blockHelperMissing";
 const HELPER_LINK: &str = "link";
+const DECORATOR_INLINE: &str = "inline";
+const LEVEL: &str = "level";
 
 type HelperValue = Option<Value>;
 
@@ -36,11 +42,23 @@ pub mod context;
 pub mod scope;
 
 pub use assert::{assert, Type};
-pub use context::{Context, MissingValue, Property};
+pub use context::{Context, Invocation, MissingValue, Property};
 pub use scope::Scope;
 
-/// Maximum stack size for helper calls
-const STACK_MAX: usize = 32;
+/// Source map entries that pair a byte range in the rendered
+/// output with the byte range of the template source that
+/// produced the output.
+pub type SourceMap = Vec<(Range<usize>, Range<usize>)>;
+
+/// Source map state for a render.
+struct SourceMapState<'render> {
+    source: &'render str,
+    span: Option<Range<usize>>,
+    entries: SourceMap,
+}
+
+/// Default maximum stack size for helper calls.
+pub(crate) const STACK_MAX: usize = 32;
 
 enum HelperTarget<'a> {
     Name(&'a str),
@@ -93,8 +111,10 @@ pub struct Render<'render> {
     registry: &'render Registry<'render>,
     local_helpers: Rc<RefCell<HashMap<String, Box<dyn LocalHelper + 'render>>>>,
     partials: HashMap<String, &'render Node<'render>>,
+    partial_blocks: Vec<(&'render Node<'render>, Option<usize>)>,
+    partial_block: Option<usize>,
     name: &'render str,
-    root: Value,
+    root: Cow<'render, Value>,
     writer: Box<&'render mut dyn Output>,
     scopes: Vec<Scope>,
     trim: TrimState,
@@ -102,6 +122,15 @@ pub struct Render<'render> {
     end_tag_hint: Option<TrimHint>,
     stack: Vec<CallSite>,
     current_partial_name: Vec<Option<&'render str>>,
+    escapes: Vec<&'render EscapeFn>,
+    indent: String,
+    line_start: bool,
+    flush_statements: bool,
+    written: u64,
+    budget: Option<Rc<Cell<u64>>>,
+    render_data: Rc<RefCell<Map<String, Value>>>,
+    source_map: Option<SourceMapState<'render>>,
+    level: Value,
 }
 
 impl<'render> Render<'render> {
@@ -109,6 +138,7 @@ impl<'render> Render<'render> {
     ///
     /// You should not need to create a renderer directly, instead
     /// use the functions provided by the `Registry`.
+    #[allow(clippy::boxed_local)]
     pub fn new<T>(
         registry: &'render Registry<'render>,
         name: &'render str,
@@ -120,22 +150,63 @@ impl<'render> Render<'render> {
         T: Serialize,
     {
         let root = serde_json::to_value(data).map_err(RenderError::from)?;
+        Ok(Self::with_root(
+            registry,
+            name,
+            Cow::Owned(root),
+            *writer,
+            stack,
+        ))
+    }
+
+    /// Create a renderer that borrows the root data.
+    ///
+    /// Unlike [new()](Render#method.new) the data is already a `Value`
+    /// so it is used directly rather than being converted and cloned.
+    pub fn with_value(
+        registry: &'render Registry<'render>,
+        name: &'render str,
+        data: &'render Value,
+        writer: &'render mut dyn Output,
+        stack: Vec<CallSite>,
+    ) -> Self {
+        Self::with_root(registry, name, Cow::Borrowed(data), writer, stack)
+    }
+
+    fn with_root(
+        registry: &'render Registry<'render>,
+        name: &'render str,
+        root: Cow<'render, Value>,
+        writer: &'render mut dyn Output,
+        stack: Vec<CallSite>,
+    ) -> Self {
         let scopes: Vec<Scope> = Vec::new();
 
-        Ok(Self {
+        Self {
             registry,
             local_helpers: Rc::new(RefCell::new(HashMap::new())),
             partials: HashMap::new(),
+            partial_blocks: Vec::new(),
+            partial_block: None,
             name,
             root,
-            writer,
+            writer: Box::new(writer),
             scopes,
             trim: Default::default(),
             hint: None,
             end_tag_hint: None,
             stack,
             current_partial_name: Vec::new(),
-        })
+            escapes: Vec::new(),
+            indent: String::new(),
+            line_start: false,
+            flush_statements: registry.flush_statements(),
+            written: 0,
+            budget: registry.render_budget().map(|b| Rc::new(Cell::new(b))),
+            render_data: Rc::new(RefCell::new(Map::new())),
+            source_map: None,
+            level: Value::from(0),
+        }
     }
 
     /// Get the name of the template being rendered.
@@ -197,11 +268,28 @@ impl<'render> Render<'render> {
     /// Render a node by iterating it's children.
     ///
     /// The supplied node should be a document or block node.
+    ///
+    /// The output is flushed when rendering completes and after
+    /// each statement or block when the registry is configured
+    /// to flush statements.
     pub fn render(&mut self, node: &'render Node<'render>) -> RenderResult<()> {
+        let flush = self.flush_statements;
         for event in node.into_iter().event(Default::default()) {
             self.render_node(event.node, event.trim)?;
+            if flush {
+                if let Node::Statement(_) | Node::Block(_) = event.node {
+                    self.flush()?;
+                }
+            }
         }
-        Ok(())
+        self.flush()
+    }
+
+    fn flush(&mut self) -> RenderResult<()> {
+        let written = self.written;
+        self.writer
+            .flush()
+            .map_err(|e| RenderError::OutputWrite(IoError::Io(e), written))
     }
 
     /// Get a named template.
@@ -212,15 +300,90 @@ impl<'render> Render<'render> {
     /// Get a mutable reference to the output destination.
     ///
     /// You should prefer the `write()` and `write_escaped()` functions
-    /// when writing strings but if you want to write bytes directly to
-    /// the output destination you can use this reference.
+    /// when writing strings and `write_raw()` when writing bytes but
+    /// if you need direct access to the output destination you can
+    /// use this reference; bytes written using this reference are not
+    /// included in the count for an output write error.
     pub fn out(&mut self) -> &mut Box<&'render mut dyn Output> {
         &mut self.writer
     }
 
     /// Escape a value using the current escape function.
+    ///
+    /// The escape function for the template being rendered is
+    /// selected by the registry and may depend upon the file
+    /// extension of the template.
     pub fn escape(&self, val: &str) -> String {
-        (self.registry.escape())(val)
+        (self.escape_fn())(val)
+    }
+
+    /// Convert a floating point number to a value.
+    ///
+    /// JSON cannot represent `NaN` or infinity so converting them
+    /// with `Value::from()` yields `null`; helpers that compute
+    /// floating point numbers should call this instead so non-finite
+    /// results use the representation configured with
+    /// [set_non_finite_repr()](crate::Registry#method.set_non_finite_repr).
+    pub fn float(&self, value: f64) -> Value {
+        match json::stringify_non_finite(value, self.registry.non_finite_repr())
+        {
+            Some(token) => Value::String(token),
+            None => Value::from(value),
+        }
+    }
+
+    fn escape_fn(&self) -> &'render EscapeFn {
+        self.escapes
+            .last()
+            .copied()
+            .unwrap_or_else(|| self.registry.escape())
+    }
+
+    /// Set whether the output is flushed after each statement
+    /// overriding the registry setting.
+    pub(crate) fn set_flush_statements(&mut self, flush: bool) {
+        self.flush_statements = flush;
+    }
+
+    /// Collect a source map for nodes of the given template source.
+    ///
+    /// Output from nodes of other templates (such as partials) is
+    /// mapped to the node in the template that caused it to render.
+    pub(crate) fn set_source_map(&mut self, source: &'render str) {
+        self.source_map = Some(SourceMapState {
+            source,
+            span: None,
+            entries: Vec::new(),
+        });
+    }
+
+    /// Take the source map collected by this renderer.
+    pub(crate) fn take_source_map(&mut self) -> SourceMap {
+        self.source_map
+            .take()
+            .map(|state| state.entries)
+            .unwrap_or_default()
+    }
+
+    /// Record output written for the current source span.
+    fn map_source(&mut self, start: u64, amount: usize) {
+        if let Some(ref mut state) = self.source_map {
+            if let Some(ref span) = state.span {
+                let start = start as usize;
+                let end = start + amount;
+                match state.entries.last_mut() {
+                    Some((out, src)) if out.end == start && src == span => {
+                        out.end = end;
+                    }
+                    _ => state.entries.push((start..end, span.clone())),
+                }
+            }
+        }
+    }
+
+    /// Use the escape function for a template until it is popped.
+    pub(crate) fn push_template_escape(&mut self, template: &Template) {
+        self.escapes.push(self.registry.escape_for(template));
     }
 
     /// Write a string to the output destination.
@@ -238,11 +401,52 @@ impl<'render> Render<'render> {
             .map_err(HelperError::from)
     }
 
+    /// Write bytes to the output destination without trimming
+    /// or escaping.
+    ///
+    /// Use this to emit byte-exact content such as a previously
+    /// cached render; whitespace control for the current node
+    /// and the escape function are not applied.
+    pub fn write_raw(&mut self, bytes: &[u8]) -> HelperResult<usize> {
+        let written = self.written;
+        self.writer
+            .write_all(bytes)
+            .map_err(|e| RenderError::OutputWrite(IoError::Io(e), written))
+            .map_err(Box::new)
+            .map_err(HelperError::from)?;
+        self.map_source(written, bytes.len());
+        self.written += bytes.len() as u64;
+        Ok(bytes.len())
+    }
+
+    /// Set a value in the data for this render.
+    ///
+    /// Render data is shared by all helper calls for the lifetime
+    /// of the render and is not affected by scopes so cooperating
+    /// helpers can use it to accumulate state; for example, to
+    /// collect headings for a table of contents.
+    pub fn set_render_data<S: AsRef<str>>(&mut self, key: S, value: Value) {
+        self.render_data
+            .borrow_mut()
+            .insert(key.as_ref().to_string(), value);
+    }
+
+    /// Get a copy of a value in the data for this render.
+    pub fn get_render_data<S: AsRef<str>>(&self, key: S) -> Option<Value> {
+        self.render_data.borrow().get(key.as_ref()).cloned()
+    }
+
     /// Push a scope onto the stack.
     pub fn push_scope(&mut self, scope: Scope) {
         self.scopes.push(scope);
     }
 
+    /// Number of block helpers being rendered; this is zero at the
+    /// root of the template and is available as the `@level` variable.
+    pub fn level(&self) -> usize {
+        self.level.as_u64().unwrap_or(0) as usize
+    }
+
     /// Remove a scope from the stack.
     pub fn pop_scope(&mut self) -> Option<Scope> {
         self.scopes.pop()
@@ -299,6 +503,43 @@ impl<'render> Render<'render> {
         Ok(branch.or(alt))
     }
 
+    /// Assert that an expansion by a helper does not exceed the
+    /// maximum number of iterations configured for the registry.
+    ///
+    /// Helpers that iterate or generate collections should call this
+    /// with the number of iterations before performing the expansion.
+    pub fn assert_iterations(
+        &self,
+        name: &str,
+        count: usize,
+    ) -> HelperResult<()> {
+        if let Some(limit) = self.registry.max_iterations() {
+            if count > limit {
+                return Err(HelperError::from(Box::new(
+                    RenderError::IterationLimit(name.to_string(), count, limit),
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the inverse branch of a block.
+    ///
+    /// Evaluates the block conditionals using [inverse()](Render#method.inverse)
+    /// and renders the first branch that matches, typically an `else` clause;
+    /// returns whether a branch was rendered.
+    pub fn render_inverse(
+        &mut self,
+        template: &'render Node<'render>,
+    ) -> HelperResult<bool> {
+        if let Some(node) = self.inverse(template)? {
+            self.template(node)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Render an inner template.
     ///
     /// Block helpers should call this when they want to render an inner template.
@@ -321,7 +562,13 @@ impl<'render> Render<'render> {
                 match node {
                     Node::Block(ref block) => {
                         let last_hint = block.trim_close();
-                        if last_hint.before {
+                        // When there are conditionals the inner template
+                        // ends at the first else tag
+                        let before = match block.conditions().first() {
+                            Some(condition) => condition.trim().before,
+                            None => last_hint.before,
+                        };
+                        if before {
                             trim.end = true;
                         }
                         hint = Some(last_hint);
@@ -341,6 +588,19 @@ impl<'render> Render<'render> {
         Ok(())
     }
 
+    /// Get the template source of the inner content of a block.
+    ///
+    /// Returns the original text between the open and close tags
+    /// so that helpers can pre-process the body of a normal block
+    /// before rendering it, for example, using [once()](Render#method.once);
+    /// for nodes other than blocks the empty string is returned.
+    pub fn block_source<'a>(&self, node: &'a Node<'a>) -> &'a str {
+        match node {
+            Node::Block(ref block) => block.inner(),
+            _ => "",
+        }
+    }
+
     /// Render a node and buffer the result to a string.
     ///
     /// The call stack and scopes are inherited from this renderer.
@@ -351,18 +611,8 @@ impl<'render> Render<'render> {
         node: &'render Node<'render>,
     ) -> Result<String, HelperError> {
         let mut writer = StringOutput::new();
-        let mut rc = Render::new(
-            self.registry,
-            self.name,
-            &self.root,
-            Box::new(&mut writer),
-            self.stack.clone(),
-        )
-        .map_err(Box::new)?;
-
-        // Inherit the stack and scope from this renderer
-        rc.stack = self.stack.clone();
-        rc.scopes = self.scopes.clone();
+        // Inherit the stack, scope, budget and data from this renderer
+        let mut rc = self.nested(&mut writer);
 
         // NOTE: call `template()` not `render()` so trim settings
         // NOTE: on the parent node are respected!
@@ -374,6 +624,77 @@ impl<'render> Render<'render> {
         Ok(writer.into())
     }
 
+    /// Render a partial by name and buffer the result to a string.
+    ///
+    /// Inline partials take precedence over templates in the registry;
+    /// see [set_relative_partials()](crate::Registry#method.set_relative_partials)
+    /// for relative names.
+    ///
+    /// When a context is given it is used as the scope for the partial
+    /// otherwise the partial inherits the current scope. The call stack
+    /// is inherited from this renderer so cyclic partials are detected.
+    pub fn render_partial_to_string(
+        &self,
+        name: &str,
+        context: Option<Value>,
+    ) -> HelperResult<String> {
+        let name = &self.resolve_partial_name(name.to_string());
+        let site = CallSite::Partial(name.to_string());
+        let not_found = || {
+            HelperError::Render(Box::new(RenderError::PartialNotFound(
+                name.to_string(),
+            )))
+        };
+
+        // The partial block renders with the enclosing partial block
+        // active so nested partial blocks always terminate
+        let mut partial_block = self.partial_block;
+        let (node, template) = if name == PARTIAL_BLOCK {
+            let index = self.partial_block.ok_or_else(not_found)?;
+            let (node, outer) = self.partial_blocks[index];
+            partial_block = outer;
+            (node, None)
+        } else {
+            if self.stack.contains(&site) {
+                return Err(HelperError::Render(Box::new(
+                    RenderError::PartialCycle(site.into()),
+                )));
+            }
+            match self.partials.get(name) {
+                Some(node) => (*node, None),
+                None => {
+                    let template =
+                        self.get_template(name).ok_or_else(not_found)?;
+                    (template.node(), Some(template))
+                }
+            }
+        };
+
+        let mut writer = StringOutput::new();
+        let mut rc = self.nested(&mut writer);
+        rc.stack.push(site);
+        rc.partial_block = partial_block;
+        if let Some(template) = template {
+            rc.current_partial_name.push(template.file_name());
+            rc.push_template_escape(template);
+        }
+
+        if let Some(context) = context {
+            let mut scope = Scope::new();
+            scope.set_base_value(context);
+            rc.push_scope(scope);
+        }
+
+        for event in node.into_iter().event(rc.hint) {
+            rc.render_node(event.node, event.trim).map_err(Box::new)?;
+        }
+
+        // Must drop the renderer to take ownership of the string buffer
+        drop(rc);
+
+        Ok(writer.into())
+    }
+
     /// Evaluate a path and return the resolved value.
     ///
     /// This allows helpers to find variables in the template data
@@ -424,11 +745,13 @@ impl<'render> Render<'render> {
         //println!("Lookup path {:?}", path);
 
 
-        // Absolute paths should never be resolved to variables
-        // the correct syntax is to use `@root` to resolve a 
-        // variable from the root of the template data.
+        // Absolute paths resolve from the root of the template
+        // data regardless of scope, equivalent to `@root`.
         if path.absolute() {
-            return None; 
+            return json::find_parts(
+                path.components().iter().map(|c| c.as_value()),
+                &self.root,
+            );
         }
 
         // Handle explicit `@root` reference
@@ -462,7 +785,12 @@ impl<'render> Render<'render> {
         // Handle local @variable references which must
         // be resolved using the current scope
         } else if path.is_local() {
-            if let Some(scope) = self.scopes.last() {
+            let components = path.components();
+            if components.len() == 1
+                && components[0].as_value().strip_prefix('@') == Some(LEVEL)
+            {
+                Some(&self.level)
+            } else if let Some(scope) = self.scopes.last() {
                 json::find_parts(
                     path.components().iter().map(|c| c.as_value()),
                     scope.locals(),
@@ -505,6 +833,24 @@ impl<'render> Render<'render> {
                 None
             }
         } else {
+            // Block parameters in the closest scope take precedence
+            if let Some(first) = path.components().first() {
+                for scope in self.scopes.iter().rev() {
+                    if let Some(value) = scope.block_param(first.as_value()) {
+                        if path.components().len() == 1 {
+                            return Some(value);
+                        }
+                        return json::find_parts(
+                            path.components()
+                                .iter()
+                                .skip(1)
+                                .map(|c| c.as_value()),
+                            value,
+                        );
+                    }
+                }
+            }
+
             let mut values: Vec<(&Value, Option<&Value>)> = self
                 .scopes
                 .iter()
@@ -642,17 +988,31 @@ impl<'render> Render<'render> {
         };
 
         let amount = self.stack.iter().filter(|&n| *n == site).count();
-        if amount >= STACK_MAX {
+        if amount >= self.registry.max_stack_depth() {
             return Err(RenderError::HelperCycle(site.into()));
         }
         self.stack.push(site);
+        // Inline partials declared in a block are scoped to the block
+        let partials = content.map(|_| self.partials.clone());
+        if content.is_some() {
+            self.level = Value::from(self.level() + 1);
+        }
 
         let mut missing: Vec<MissingValue> = Vec::new();
         let args = self.arguments(call, &mut missing)?;
         let hash = self.hash(call, &mut missing)?;
+        let invocation = if text.is_some() {
+            Invocation::Raw
+        } else if content.is_some() {
+            Invocation::Block
+        } else {
+            Invocation::Statement
+        };
         let mut context = Context::new(
             call,
             name.to_owned(),
+            self.current_name().to_owned(),
+            invocation,
             args,
             hash,
             text,
@@ -680,6 +1040,10 @@ impl<'render> Render<'render> {
 
         drop(local_helpers);
 
+        if let Some(partials) = partials {
+            self.partials = partials;
+            self.level = Value::from(self.level() - 1);
+        }
         self.stack.pop();
 
         Ok(value)
@@ -690,16 +1054,30 @@ impl<'render> Render<'render> {
             || self.registry.helpers().get(name).is_some()
     }
 
+    /// Error for a variable that could not be resolved in strict mode.
+    fn variable_not_found(&self, path: &Path<'_>) -> RenderError {
+        // More parent references than scopes
+        let depth = self.scopes.len();
+        if path.parents() as usize > depth {
+            return RenderError::ParentScopeOutOfRange(
+                path.as_str().to_string(),
+                path.parents() as usize,
+                depth,
+            );
+        }
+        RenderError::VariableNotFound(
+            path.as_str().to_string(),
+            self.name.to_string(),
+        )
+    }
+
     // Fallible version of path lookup.
     fn resolve(&mut self, path: &Path<'_>) -> RenderResult<HelperValue> {
         if let Some(value) = self.lookup(path).cloned().take() {
             Ok(Some(value))
         } else {
             if self.registry.strict() {
-                Err(RenderError::VariableNotFound(
-                    path.as_str().to_string(),
-                    self.name.to_string(),
-                ))
+                Err(self.variable_not_found(path))
             } else {
                 // TODO: call a missing_variable handler?
                 Ok(None)
@@ -743,12 +1121,19 @@ impl<'render> Render<'render> {
                                     None,
                                 );
                             } else {
-                                // TODO: also error if Call has arguments or parameters
                                 if self.registry.strict() {
-                                    return Err(RenderError::VariableNotFound(
-                                        path.as_str().to_string(),
-                                        self.name.to_string(),
-                                    ));
+                                    // Arguments or parameters mean this
+                                    // is a helper invocation not a variable
+                                    if !call.arguments().is_empty()
+                                        || !call.parameters().is_empty()
+                                    {
+                                        return Err(
+                                            RenderError::HelperNotFound(
+                                                path.as_str().to_string(),
+                                            ),
+                                        );
+                                    }
+                                    return Err(self.variable_not_found(path));
                                 }
                             }
                         }
@@ -763,7 +1148,10 @@ impl<'render> Render<'render> {
     }
 
     fn statement(&mut self, call: &Call<'_>) -> RenderResult<HelperValue> {
-        if call.is_partial() {
+        if call.is_decorator() {
+            self.decorator(call, None)?;
+            Ok(None)
+        } else if call.is_partial() {
             self.render_partial(call, None)?;
             Ok(None)
         } else {
@@ -779,7 +1167,7 @@ impl<'render> Render<'render> {
             CallTarget::Path(ref path) => {
                 if path.as_str() == PARTIAL_BLOCK {
                     return Ok(PARTIAL_BLOCK.to_string());
-                } else if path.is_simple() {
+                } else if path.is_identifiers() {
                     return Ok(path.as_str().to_string());
                 } else {
                     return Err(RenderError::PartialIdentifier(
@@ -794,12 +1182,105 @@ impl<'render> Render<'render> {
         }
     }
 
+    /// Invoke a decorator.
+    ///
+    /// The only supported decorator is `inline` which registers the
+    /// inner template as a partial for the remainder of the render.
+    fn decorator(
+        &mut self,
+        call: &Call<'_>,
+        template: Option<&'render Node<'render>>,
+    ) -> RenderResult<()> {
+        let name = call.target().as_str();
+        if name != DECORATOR_INLINE {
+            return Err(RenderError::DecoratorNotFound(name.to_string()));
+        }
+
+        let mut missing: Vec<MissingValue> = Vec::new();
+        let arguments = self.arguments(call, &mut missing)?;
+        match (arguments.first(), template) {
+            (Some(Value::String(name)), Some(node)) if arguments.len() == 1 => {
+                self.partials.insert(name.to_string(), node);
+                Ok(())
+            }
+            _ => Err(RenderError::InlinePartial(call.as_str().to_string())),
+        }
+    }
+
+    /// Register a partial node for the lifetime of this render.
+    ///
+    /// Partials registered this way take precedence over templates
+    /// in the registry with the same name.
+    pub(crate) fn insert_partial(
+        &mut self,
+        name: String,
+        node: &'render Node<'render>,
+    ) {
+        self.partials.insert(name, node);
+    }
+
+    /// Resolve a partial name relative to the directory of the
+    /// partial or template being rendered when relative partials
+    /// are enabled and the relative template exists.
+    fn resolve_partial_name(&self, name: String) -> String {
+        if !self.registry.relative_partials()
+            || name == PARTIAL_BLOCK
+            || self.partials.contains_key(&name)
+        {
+            return name;
+        }
+
+        let current = self
+            .stack
+            .iter()
+            .rev()
+            .find_map(|site| match site {
+                CallSite::Partial(ref name) => Some(name.as_str()),
+                _ => None,
+            })
+            .unwrap_or(self.name);
+
+        // A partial with the same name as the current partial refers
+        // to the global partial rather than recursing into itself
+        if let Some((dir, _)) = current.rsplit_once('/') {
+            let relative = format!("{}/{}", dir, name);
+            if relative != current && self.get_template(&relative).is_some() {
+                return relative;
+            }
+        }
+        name
+    }
+
     fn render_partial(
         &mut self,
         call: &Call<'_>,
         partial_block: Option<&'render Node<'render>>,
+    ) -> RenderResult<()> {
+        // Each line of output for a standalone partial is indented
+        let indent = self.indent.len();
+        if let Some(value) = call.indent() {
+            self.indent.push_str(value);
+            self.line_start = true;
+        }
+        // Inline partials declared by the partial are scoped to it
+        let partials = self.partials.clone();
+        let result = self.render_partial_call(call, partial_block);
+        self.partials = partials;
+        self.indent.truncate(indent);
+        result
+    }
+
+    fn render_partial_call(
+        &mut self,
+        call: &Call<'_>,
+        partial_block: Option<&'render Node<'render>>,
     ) -> RenderResult<()> {
         let name = self.get_partial_name(call)?;
+        let name = self.resolve_partial_name(name);
+
+        if name == PARTIAL_BLOCK {
+            return self.render_partial_block(call, partial_block);
+        }
 
         let site = CallSite::Partial(name.to_string());
         if self.stack.contains(&site) {
@@ -807,22 +1288,115 @@ impl<'render> Render<'render> {
         }
         self.stack.push(site);
 
+        // Partial blocks are stacked so that `@partial-block`
+        // resolves to the block passed to the nearest partial
+        let outer_block = self.partial_block;
         if let Some(node) = partial_block {
-            self.partials.insert(PARTIAL_BLOCK.to_string(), node);
+            self.partial_blocks.push((node, outer_block));
+            self.partial_block = Some(self.partial_blocks.len() - 1);
+
+            // Inline partials in a partial block are available
+            // to the partial; typically used for layouts
+            if let Node::Block(ref block) = node {
+                for child in block.nodes() {
+                    if let Node::Block(ref inline) = child {
+                        if inline.call().is_decorator() {
+                            self.decorator(inline.call(), Some(child))?;
+                        }
+                    }
+                }
+            }
         }
 
+        let result = self.render_partial_node(call, name);
+
+        if partial_block.is_some() {
+            self.partial_blocks.pop();
+            self.partial_block = outer_block;
+        }
+        self.stack.pop();
+
+        result
+    }
+
+    /// Render the block passed to the enclosing partial.
+    ///
+    /// When no block was passed the inner template of a
+    /// `{{#> @partial-block}}` call is rendered as the default content.
+    ///
+    /// Whilst the block is rendered `@partial-block` refers to the
+    /// block that was active when the enclosing partial was called.
+    fn render_partial_block(
+        &mut self,
+        call: &Call<'_>,
+        default_block: Option<&'render Node<'render>>,
+    ) -> RenderResult<()> {
+        let current = self.partial_block;
+        let node = match current {
+            Some(index) => {
+                let (node, outer) = self.partial_blocks[index];
+                self.partial_block = outer;
+                node
+            }
+            None => default_block.ok_or_else(|| {
+                RenderError::PartialNotFound(PARTIAL_BLOCK.to_string())
+            })?,
+        };
+
+        let site = CallSite::Partial(PARTIAL_BLOCK.to_string());
+        self.stack.push(site);
+        let result = self.render_partial_scope(call, node);
+        self.stack.pop();
+        self.partial_block = current;
+        result
+    }
+
+    fn render_partial_node(
+        &mut self,
+        call: &Call<'_>,
+        name: String,
+    ) -> RenderResult<()> {
+        let mut is_template = false;
         let node = if let Some(local_partial) = self.partials.get(&name) {
             local_partial
         } else {
-            let template = self
-                .get_template(&name)
-                .ok_or_else(|| RenderError::PartialNotFound(name))?;
+            let template = match call.target() {
+                CallTarget::SubExpr(ref sub) => {
+                    self.get_template(&name).ok_or_else(|| {
+                        RenderError::DynamicPartialNotFound(
+                            name.clone(),
+                            sub.as_str().to_string(),
+                        )
+                    })?
+                }
+                CallTarget::Path(_) => self
+                    .get_template(&name)
+                    .ok_or_else(|| RenderError::PartialNotFound(name))?,
+            };
 
             self.current_partial_name.push(template.file_name());
+            self.push_template_escape(template);
+            is_template = true;
 
             template.node()
         };
 
+        self.render_partial_scope(call, node)?;
+
+        if is_template {
+            self.current_partial_name.pop();
+            self.escapes.pop();
+        }
+
+        Ok(())
+    }
+
+    /// Render a partial node in a scope for the partial call.
+    fn render_partial_scope(
+        &mut self,
+        call: &Call<'_>,
+        node: &'render Node<'render>,
+    ) -> RenderResult<()> {
         let mut missing: Vec<MissingValue> = Vec::new();
         let hash = self.hash(call, &mut missing)?;
         let scope = if !call.arguments().is_empty() {
@@ -833,7 +1407,26 @@ impl<'render> Render<'render> {
                 Scope::from(hash)
             }
         } else {
-            Scope::from(hash)
+            // Without a context argument the partial inherits the
+            // current context merged with any hash parameters
+            let base = self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|s| s.base_value().as_ref());
+            match base {
+                Some(Value::Object(base)) => {
+                    let mut merged = base.clone();
+                    merged.extend(hash);
+                    Scope::from(merged)
+                }
+                Some(base) if hash.is_empty() => {
+                    let mut scope = Scope::new();
+                    scope.set_base_value(base.clone());
+                    scope
+                }
+                _ => Scope::from(hash),
+            }
         };
 
         self.scopes.push(scope);
@@ -845,9 +1438,6 @@ impl<'render> Render<'render> {
         }
         self.scopes.pop();
 
-        self.current_partial_name.pop();
-        self.stack.pop();
-
         Ok(())
     }
 
@@ -922,7 +1512,9 @@ impl<'render> Render<'render> {
         let call = block.call();
         let raw = block.is_raw();
 
-        if call.is_partial() {
+        if call.is_decorator() {
+            self.decorator(call, Some(node))?;
+        } else if call.is_partial() {
             self.render_partial(call, Some(node))?;
         } else {
             match call.target() {
@@ -975,6 +1567,10 @@ impl<'render> Render<'render> {
                                 text,
                                 None,
                             )?;
+
+                            // Trim after the end tag regardless of the
+                            // branch rendered by the helper
+                            self.end_tag_hint = Some(block.trim_close());
                         } else {
                             return self.block_helper_missing(
                                 node, block, call, text, raw,
@@ -1001,9 +1597,24 @@ impl<'render> Render<'render> {
         link: &'render Link<'render>,
     ) -> RenderResult<()> {
         let lines = link.lines();
-        let href = Value::String(link.href().to_string());
-        let label = Value::String(link.label().to_string());
-        let title = Value::String(link.title().to_string());
+        let href = if let Some(ref resolver) =
+            self.registry.handlers().link_resolver
+        {
+            Value::String(resolver(link.href()))
+        } else {
+            Value::String(link.href().to_string())
+        };
+        let (label, title) = if self.registry.render_link_labels() {
+            (
+                Value::String(self.render_fragment(link.label(), lines)?),
+                Value::String(self.render_fragment(link.title(), lines)?),
+            )
+        } else {
+            (
+                Value::String(link.label().to_string()),
+                Value::String(link.title().to_string()),
+            )
+        };
 
         // Build a call so that the helper invocation flows
         // through the standard logic.
@@ -1039,14 +1650,77 @@ impl<'render> Render<'render> {
         Ok(())
     }
 
+    // Render a fragment of template content using the current
+    // state of this renderer without escaping the output.
+    fn render_fragment(
+        &self,
+        content: &str,
+        lines: &Range<usize>,
+    ) -> RenderResult<String> {
+        let options =
+            ParserOptions::new(self.current_name().to_string(), lines.start, 0);
+        let template = Template::compile(content.to_string(), options)
+            .map_err(HelperError::from)?;
+        let noop: EscapeFn = Box::new(escape::noop);
+
+        let mut writer = StringOutput::new();
+        let mut rc = self.nested(&mut writer);
+        rc.escapes.push(&noop);
+        rc.render(template.node())?;
+        drop(rc);
+        Ok(writer.into())
+    }
+
+    // Create a renderer for a nested render pass that inherits
+    // the root data, scopes, call stack and partials of this renderer.
+    fn nested<'a>(&'a self, writer: &'a mut dyn Output) -> Render<'a> {
+        let mut rc = Render::with_root(
+            self.registry,
+            self.name,
+            Cow::Borrowed(&*self.root),
+            writer,
+            self.stack.clone(),
+        );
+        rc.scopes = self.scopes.clone();
+        rc.budget = self.budget.clone();
+        rc.render_data = Rc::clone(&self.render_data);
+        rc.partials = self.partials.clone();
+        rc.partial_blocks = self.partial_blocks.clone();
+        rc.partial_block = self.partial_block;
+        rc.current_partial_name = self.current_partial_name.clone();
+        rc.escapes = self.escapes.clone();
+        rc.level = self.level.clone();
+        rc
+    }
+
     pub(crate) fn render_node(
         &mut self,
         node: &'render Node<'render>,
         trim: TrimState,
     ) -> RenderResult<()> {
+        if let Some(ref budget) = self.budget {
+            if budget.get() == 0 {
+                return Err(RenderError::BudgetExceeded(
+                    self.registry.render_budget().unwrap_or(0),
+                ));
+            }
+            budget.set(budget.get() - 1);
+        }
+
         self.trim = trim;
         self.hint = Some(node.trim());
 
+        // Output is mapped to the innermost node of the source template
+        let mut outer_span = None;
+        if let Some(ref mut state) = self.source_map {
+            let source = node.source();
+            if source.as_ptr() == state.source.as_ptr()
+                && source.len() == state.source.len()
+            {
+                outer_span = Some(state.span.replace(node.span()));
+            }
+        }
+
         if let Some(hint) = self.end_tag_hint.take() {
             if hint.after {
                 self.trim.start = true;
@@ -1058,7 +1732,11 @@ impl<'render> Render<'render> {
                 self.write_str(n.as_str(), false)?;
             }
             Node::RawStatement(ref n) => {
-                let raw = &n.as_str()[1..];
+                let raw = if self.registry.strip_raw_escape() {
+                    &n.as_str()[1..]
+                } else {
+                    n.as_str()
+                };
                 self.write_str(raw, false)?;
             }
             Node::Link(ref n) => {
@@ -1090,6 +1768,10 @@ impl<'render> Render<'render> {
             }
         }
 
+        if let (Some(span), Some(state)) = (outer_span, &mut self.source_map) {
+            state.span = span;
+        }
+
         Ok(())
     }
 
@@ -1100,11 +1782,34 @@ impl<'render> Render<'render> {
             return Ok(0);
         }
 
-        if escape {
-            let escaped = (self.registry.escape())(val);
-            Ok(self.writer.write_str(&escaped).map_err(RenderError::from)?)
+        let val = if escape {
+            Cow::Owned((self.escape_fn())(val))
         } else {
-            Ok(self.writer.write_str(val).map_err(RenderError::from)?)
+            Cow::Borrowed(val)
+        };
+        let val = self.indent_lines(val);
+        let result = self.writer.write_str(&val);
+        let written = self.written;
+        let amount = result
+            .map_err(|e| RenderError::OutputWrite(IoError::Io(e), written))?;
+        self.map_source(written, amount);
+        self.written += amount as u64;
+        Ok(amount)
+    }
+
+    // Indent each line when rendering a standalone partial.
+    fn indent_lines<'a>(&mut self, s: Cow<'a, str>) -> Cow<'a, str> {
+        if self.indent.is_empty() {
+            return s;
+        }
+        let mut value = String::with_capacity(s.len() + self.indent.len());
+        for line in s.split_inclusive('\n') {
+            if self.line_start {
+                value.push_str(&self.indent);
+            }
+            value.push_str(line);
+            self.line_start = line.ends_with('\n');
         }
+        Cow::Owned(value)
     }
 }
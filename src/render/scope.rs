@@ -11,6 +11,7 @@ use serde_json::{Map, Value};
 pub struct Scope {
     value: Option<Value>,
     locals: Value,
+    block_params: Map<String, Value>,
 }
 
 impl Scope {
@@ -19,6 +20,7 @@ impl Scope {
         Self {
             locals: Value::Object(Map::new()),
             value: None,
+            block_params: Map::new(),
         }
     }
 
@@ -45,6 +47,20 @@ impl Scope {
         self.locals.as_object().unwrap().get(name)
     }
 
+    /// Set a block parameter.
+    ///
+    /// Block parameters are declared in the open tag of a block
+    /// (`{{#each items as |item|}}`) and are resolved by name before
+    /// the base value of any scope.
+    pub fn set_block_param(&mut self, name: &str, value: Value) {
+        self.block_params.insert(name.to_string(), value);
+    }
+
+    /// Get a named block parameter.
+    pub fn block_param(&self, name: &str) -> Option<&Value> {
+        self.block_params.get(name)
+    }
+
     /// Set the base value for the scope.
     ///
     /// When the renderer resolves variables if they
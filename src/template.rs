@@ -2,11 +2,15 @@
 use std::collections::HashMap;
 
 use serde::Serialize;
+use serde_json::Value;
 use std::fmt;
 
 use crate::{
     output::Output,
-    parser::{ast::Node, Parser, ParserOptions},
+    parser::{
+        ast::Node, delimiter, format, Delimiters, FormatOptions, Parser,
+        ParserOptions,
+    },
     render::{CallSite, Render},
     Registry, RenderResult, SyntaxResult,
 };
@@ -16,9 +20,17 @@ use self_cell::self_cell;
 /// Collection of named templates.
 pub type Templates = HashMap<String, Template>;
 
+/// Template source and the source rewritten to use
+/// the default delimiters.
+#[derive(Debug)]
+struct Source {
+    content: String,
+    canonical: Option<String>,
+}
+
 self_cell!(
     struct Ast {
-        owner: String,
+        owner: Source,
 
         #[covariant]
         dependent: Node,
@@ -33,6 +45,7 @@ self_cell!(
 #[derive(Debug)]
 pub struct Template {
     file_name: Option<String>,
+    extension: Option<String>,
     ast: Ast,
 }
 
@@ -41,6 +54,49 @@ impl Template {
     pub fn compile(
         source: String,
         options: ParserOptions,
+    ) -> SyntaxResult<Self> {
+        Self::compile_source(
+            Source {
+                content: source,
+                canonical: None,
+            },
+            options,
+            false,
+        )
+    }
+
+    /// Compile a new template that uses custom delimiters.
+    pub fn compile_delimited(
+        source: String,
+        options: ParserOptions,
+        delimiters: &Delimiters,
+    ) -> SyntaxResult<Self> {
+        Self::compile_with(source, options, Some(delimiters), false)
+    }
+
+    /// Compile a new template with optional custom delimiters
+    /// and whether a byte order mark is treated as text.
+    pub(crate) fn compile_with(
+        source: String,
+        options: ParserOptions,
+        delimiters: Option<&Delimiters>,
+        preserve_bom: bool,
+    ) -> SyntaxResult<Self> {
+        let canonical = delimiters.map(|d| d.rewrite(&source));
+        Self::compile_source(
+            Source {
+                content: source,
+                canonical,
+            },
+            options,
+            preserve_bom,
+        )
+    }
+
+    fn compile_source(
+        source: Source,
+        options: ParserOptions,
+        preserve_bom: bool,
     ) -> SyntaxResult<Self> {
         let mut err = None;
 
@@ -50,9 +106,17 @@ impl Template {
             None
         };
 
-        let ast = Ast::new(source, |s: &String| {
-            match Parser::new(s, options).parse() {
-                Ok(ast) => ast,
+        let ast = Ast::new(source, |s: &Source| {
+            let text = s.canonical.as_ref().unwrap_or(&s.content);
+            let mut parser = Parser::new(text, options);
+            parser.set_preserve_bom(preserve_bom);
+            match parser.parse() {
+                Ok(mut ast) => {
+                    if s.canonical.is_some() {
+                        delimiter::restore(&mut ast, &s.content);
+                    }
+                    ast
+                }
                 Err(e) => {
                     err = Some(e);
                     Default::default()
@@ -63,7 +127,11 @@ impl Template {
         if let Some(e) = err {
             Err(e)
         } else {
-            Ok(Self { file_name, ast })
+            Ok(Self {
+                file_name,
+                extension: None,
+                ast,
+            })
         }
     }
 
@@ -77,6 +145,40 @@ impl Template {
         self.file_name.as_ref().map(|s| s.as_str())
     }
 
+    /// Get the file extension that selects the escape function
+    /// for this template.
+    ///
+    /// Only set for templates loaded from files when an escape
+    /// function is registered for the file extension; see
+    /// [set_escape_by_extension()](crate::Registry#method.set_escape_by_extension).
+    pub fn extension(&self) -> Option<&str> {
+        self.extension.as_deref()
+    }
+
+    #[cfg(feature = "fs")]
+    pub(crate) fn set_extension(&mut self, extension: Option<String>) {
+        self.extension = extension;
+    }
+
+    /// Visit every node in this template.
+    ///
+    /// Performs a depth-first traversal starting with the document
+    /// node; block nodes are visited before their children followed
+    /// by each conditional branch and the branch children.
+    pub fn walk<F>(&self, mut visitor: F)
+    where
+        F: FnMut(&Node<'_>),
+    {
+        walk(self.node(), &mut visitor);
+    }
+
+    /// Format this template using normalized syntax.
+    ///
+    /// See [format()](crate::parser::format::format) for details.
+    pub fn format(&self, options: FormatOptions) -> String {
+        format::format(self.node(), &options)
+    }
+
     /// Render this template to the given writer.
     pub fn render<'a, T>(
         &self,
@@ -91,8 +193,47 @@ impl Template {
     {
         let mut rc =
             Render::new(registry, name, data, Box::new(writer), stack)?;
+        rc.push_template_escape(self);
         rc.render(self.node())
     }
+
+    /// Render this template to the given writer borrowing
+    /// the root data.
+    pub fn render_value<'a>(
+        &self,
+        registry: &'a Registry<'a>,
+        name: &'a str,
+        data: &'a Value,
+        writer: &'a mut impl Output,
+        stack: Vec<CallSite>,
+    ) -> RenderResult<()> {
+        let mut rc = Render::with_value(registry, name, data, writer, stack);
+        rc.push_template_escape(self);
+        rc.render(self.node())
+    }
+}
+
+fn walk<F>(node: &Node<'_>, visitor: &mut F)
+where
+    F: FnMut(&Node<'_>),
+{
+    visitor(node);
+    match node {
+        Node::Document(ref doc) => {
+            for child in doc.nodes() {
+                walk(child, visitor);
+            }
+        }
+        Node::Block(ref block) => {
+            for child in block.nodes() {
+                walk(child, visitor);
+            }
+            for condition in block.conditions() {
+                walk(condition, visitor);
+            }
+        }
+        _ => {}
+    }
 }
 
 impl fmt::Display for Template {
@@ -1,5 +1,7 @@
 //! Types that control how whitespace is trimmed.
-use crate::parser::ast::Node;
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::ast::{Element, Node, Slice};
 
 /// State that indicates how whitespace should be trimmed
 /// from the node being rendered.
@@ -41,3 +43,222 @@ pub struct TrimHint {
     /// Whether the next node should have leading whitespace removed.
     pub after: bool,
 }
+
+/// Remove the lines occupied by standalone tags.
+///
+/// A block open, else or close tag, partial or comment is standalone
+/// when it is the only content on a line; the spaces and tabs before
+/// the tag and after it including the newline are removed so the line
+/// does not appear in the rendered output. The whitespace before a
+/// standalone partial is kept as the indentation for the partial.
+///
+/// The node should be a document; other nodes are ignored.
+pub(crate) fn standalone(node: &mut Node<'_>) {
+    if let Node::Document(ref mut doc) = node {
+        let mut marks: Standalone = Default::default();
+        marks.collect(doc.nodes(), true);
+        marks.apply(doc.nodes_mut());
+    }
+}
+
+/// Text nodes to strip keyed by the start of the original span.
+///
+/// Whitespace detection must use the original text so all nodes
+/// are marked before any text is modified.
+#[derive(Default)]
+struct Standalone {
+    start: HashSet<usize>,
+    end: HashSet<usize>,
+    indent: HashMap<usize, usize>,
+}
+
+impl Standalone {
+    fn collect<'a>(&mut self, nodes: &'a [Node<'a>], root: bool) {
+        for (i, node) in nodes.iter().enumerate() {
+            match node {
+                Node::Comment(_) | Node::RawComment(_) => {
+                    self.inline(nodes, i, root);
+                }
+                Node::Statement(ref call) if call.is_partial() => {
+                    let standalone = self.inline(nodes, i, root);
+                    if standalone {
+                        self.mark_indent(nodes, i, call.span().start);
+                    }
+                }
+                Node::Block(ref block) if !block.is_raw() => {
+                    let mut bodies: Vec<&[Node<'_>]> = vec![block.nodes()];
+                    for condition in block.conditions() {
+                        if let Node::Block(ref condition) = condition {
+                            bodies.push(condition.nodes());
+                        }
+                    }
+
+                    for body in bodies.iter() {
+                        self.collect(body, false);
+                    }
+
+                    // Open tag
+                    let first = bodies.first().unwrap();
+                    if is_prev_whitespace(nodes, i, root)
+                        && is_next_whitespace(first, 0, false)
+                    {
+                        self.mark_end(nodes, i);
+                        self.mark_start(first, 0);
+                    }
+
+                    // Else tags
+                    for pair in bodies.windows(2) {
+                        let (before, after) = (pair[0], pair[1]);
+                        if is_prev_whitespace(before, before.len(), false)
+                            && is_next_whitespace(after, 0, false)
+                        {
+                            self.mark_end(before, before.len());
+                            self.mark_start(after, 0);
+                        }
+                    }
+
+                    // Close tag
+                    let last = bodies.last().unwrap();
+                    if is_prev_whitespace(last, last.len(), false)
+                        && is_next_whitespace(nodes, i + 1, root)
+                    {
+                        self.mark_end(last, last.len());
+                        self.mark_start(nodes, i + 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn inline(&mut self, nodes: &[Node<'_>], index: usize, root: bool) -> bool {
+        let standalone = is_prev_whitespace(nodes, index, root)
+            && is_next_whitespace(nodes, index + 1, root);
+        if standalone {
+            self.mark_end(nodes, index);
+            self.mark_start(nodes, index + 1);
+        }
+        standalone
+    }
+
+    /// Record the whitespace before the partial at `index` as the
+    /// indentation for the partial keyed by the start of the call.
+    fn mark_indent(&mut self, nodes: &[Node<'_>], index: usize, key: usize) {
+        if index == 0 {
+            return;
+        }
+        if let Some(Node::Text(ref text)) = nodes.get(index - 1) {
+            let value = text.as_str();
+            let line = value.rfind('\n').map(|pos| &value[pos + 1..]);
+            let indent = line.unwrap_or(value);
+            if !indent.is_empty() {
+                self.indent.insert(key, indent.len());
+            }
+        }
+    }
+
+    /// Mark the text node at `index` to strip leading whitespace
+    /// up to and including the first newline.
+    fn mark_start(&mut self, nodes: &[Node<'_>], index: usize) {
+        if let Some(Node::Text(ref text)) = nodes.get(index) {
+            self.start.insert(text.span().start);
+        }
+    }
+
+    /// Mark the text node before `index` to strip trailing
+    /// whitespace after the last newline.
+    fn mark_end(&mut self, nodes: &[Node<'_>], index: usize) {
+        if index == 0 {
+            return;
+        }
+        if let Some(Node::Text(ref text)) = nodes.get(index - 1) {
+            self.end.insert(text.span().start);
+        }
+    }
+
+    fn apply(&self, nodes: &mut Vec<Node<'_>>) {
+        for node in nodes.iter_mut() {
+            match node {
+                Node::Text(ref mut text) => {
+                    let key = text.span().start;
+                    if self.start.contains(&key) {
+                        let value = text.as_str().trim_start_matches(is_blank);
+                        let value = value
+                            .strip_prefix("\r\n")
+                            .or_else(|| value.strip_prefix('\n'))
+                            .unwrap_or(value);
+                        text.span_mut().start = text.span().end - value.len();
+                    }
+                    if self.end.contains(&key) {
+                        let value = text.as_str().trim_end_matches(is_blank);
+                        text.span_mut().end = text.span().start + value.len();
+                    }
+                }
+                Node::Statement(ref mut call) => {
+                    if let Some(length) = self.indent.get(&call.span().start) {
+                        call.set_indent(*length);
+                    }
+                }
+                Node::Block(ref mut block) if !block.is_raw() => {
+                    self.apply(block.nodes_mut());
+                    for condition in block.conditions_mut() {
+                        if let Node::Block(ref mut condition) = condition {
+                            self.apply(condition.nodes_mut());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Whitespace that may surround a standalone tag on a line.
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+fn is_blank_line(value: &str) -> bool {
+    value.chars().all(is_blank)
+}
+
+/// Determine if the node before `index` is text that ends a line.
+///
+/// At the start of the document any whitespace counts.
+fn is_prev_whitespace(nodes: &[Node<'_>], index: usize, root: bool) -> bool {
+    if index == 0 {
+        return root;
+    }
+    match nodes[index - 1] {
+        Node::Text(ref text) => {
+            let value = text.as_str();
+            match value.rfind('\n') {
+                Some(pos) => is_blank_line(&value[pos + 1..]),
+                None => root && index == 1 && is_blank_line(value),
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Determine if the node at `index` is text that starts a new line.
+///
+/// At the end of the document any whitespace counts.
+fn is_next_whitespace(nodes: &[Node<'_>], index: usize, root: bool) -> bool {
+    match nodes.get(index) {
+        Some(Node::Text(ref text)) => {
+            let value = text.as_str();
+            match value.find('\n') {
+                Some(pos) => {
+                    let line = &value[..pos];
+                    is_blank_line(line.strip_suffix('\r').unwrap_or(line))
+                }
+                None => {
+                    root && index == nodes.len() - 1 && is_blank_line(value)
+                }
+            }
+        }
+        Some(_) => false,
+        None => root,
+    }
+}
@@ -0,0 +1,145 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "array.rs";
+
+#[test]
+fn array_first() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{first list}}|{{first empty}}";
+    let data = json!({"list": ["a", "b", "c"], "empty": []});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("a|", &result);
+    Ok(())
+}
+
+#[test]
+fn array_last() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{last list}}|{{last empty}}";
+    let data = json!({"list": ["a", "b", "c"], "empty": []});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("c|", &result);
+    Ok(())
+}
+
+#[test]
+fn array_first_count() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each (first list 2)}}{{this}}{{/each}}";
+    let data = json!({"list": ["a", "b", "c"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("ab", &result);
+    Ok(())
+}
+
+#[test]
+fn array_last_count() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each (last list 2)}}{{this}}{{/each}}";
+    let data = json!({"list": ["a", "b", "c"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("bc", &result);
+    let value = r"{{#each (last list 5)}}{{this}}{{/each}}";
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("abc", &result);
+    Ok(())
+}
+
+#[test]
+fn array_type_assert() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"list": "abc"});
+    assert!(registry.once(NAME, r"{{first list}}", &data).is_err());
+    assert!(registry.once(NAME, r"{{last list -1}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn array_range() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"start": 1, "end": 4});
+    let cases = [
+        (r"{{#each (range 0 10 2)}}{{this}},{{/each}}", "0,2,4,6,8,"),
+        (r"{{#each (range start end)}}{{this}},{{/each}}", "1,2,3,"),
+        (r"{{#each (range 3 0 -1)}}{{this}},{{/each}}", "3,2,1,"),
+        (r"{{#each (range -2 1)}}{{this}},{{/each}}", "-2,-1,0,"),
+        (
+            r"{{#each (range 5 5)}}{{this}}{{else}}empty{{/each}}",
+            "empty",
+        ),
+        (
+            r"{{#each (range 5 0)}}{{this}}{{else}}empty{{/each}}",
+            "empty",
+        ),
+        (r"{{json (range 0 7 3)}}", "[0,3,6]"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn array_range_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    let cases = [
+        r"{{range 0 10 0}}",
+        r"{{range 0.5 10}}",
+        r#"{{range "0" 10}}"#,
+        r"{{range 0}}",
+        r"{{range 0 100000}}",
+    ];
+    for value in cases.iter() {
+        assert!(registry.once(NAME, value, &data).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn array_chunk() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"items": [1, 2, 3, 4, 5], "empty": [], "title": "foo"});
+    let cases = [
+        (
+            r"{{#chunk items 2}}[{{#each this}}{{this}}{{/each}}]{{/chunk}}",
+            "[12][34][5]",
+        ),
+        (
+            r"{{#chunk items 5}}{{@index}}:{{len this}}{{/chunk}}",
+            "0:5",
+        ),
+        (r"{{#chunk items 10}}{{json this}}{{/chunk}}", "[1,2,3,4,5]"),
+        (
+            r"{{#chunk items 3}}{{@index}}={{json this}};{{/chunk}}",
+            "0=[1,2,3];1=[4,5];",
+        ),
+        (r"{{#chunk items 4}}{{@root.title}}{{/chunk}}", "foofoo"),
+        (r"{{#chunk empty 2}}x{{else}}none{{/chunk}}", "none"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn array_chunk_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"items": [1, 2]});
+    let cases = [
+        r"{{#chunk items 0}}{{/chunk}}",
+        r"{{#chunk items -1}}{{/chunk}}",
+        r"{{#chunk items 1.5}}{{/chunk}}",
+        r"{{#chunk items}}{{/chunk}}",
+        r"{{#chunk 1 2}}{{/chunk}}",
+        r"{{chunk items 2}}",
+    ];
+    for value in cases.iter() {
+        assert!(registry.once(NAME, value, &data).is_err(), "{}", value);
+    }
+    Ok(())
+}
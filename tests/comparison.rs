@@ -62,3 +62,187 @@ fn cmp_lte() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn cmp_type_assert_location() -> Result<()> {
+    let registry = Registry::new();
+    let value = "foo\n{{gt \"a\" 1}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting type assertion error"),
+        Err(e) => assert_eq!(
+            "Helper 'gt' type assertion failed, expected 'string' but got 'number' at comparison.rs:2",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn cmp_mixed_int_float() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"count": 1.0, "total": 2});
+    let cases = [
+        (r"{{eq count 1}}", "true"),
+        (r"{{eq 1 1.0}}", "true"),
+        (r"{{ne total 2.0}}", "false"),
+        (r"{{gt total 1.5}}", "true"),
+        (r"{{gte count 1}}", "true"),
+        (r"{{lt count 1.5}}", "true"),
+        (r"{{lte total 2.0}}", "true"),
+        (r"{{lt 1.5 count}}", "false"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn cmp_large_integers() -> Result<()> {
+    let registry = Registry::new();
+    // Not distinguishable when compared as f64
+    let data = json!({"lhs": 9007199254740993u64, "rhs": 9007199254740992u64});
+    let result = registry.once(NAME, r"{{eq lhs rhs}}", &data)?;
+    assert_eq!("false", &result);
+    let result = registry.once(NAME, r"{{gt lhs rhs}}", &data)?;
+    assert_eq!("true", &result);
+    Ok(())
+}
+
+#[test]
+fn cmp_string_array() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "name": "mike",
+        "list": [1, 2],
+        "other": ["a", "b"],
+        "more": [1, 2, 3],
+        "one": [1],
+        "two": [2],
+        "same": [1, 2],
+        "reversed": [2, 1],
+        "obj": {"a": 1},
+        "other_obj": {"a": 2},
+    });
+    let cases = [
+        (r#"{{gt name "m"}}"#, "true"),
+        (r#"{{lt name "m"}}"#, "false"),
+        (r#"{{eq name "mike"}}"#, "true"),
+        (r#"{{lte "a" "b"}}"#, "true"),
+        (r#"{{gte "B" "a"}}"#, "false"),
+        (r"{{eq list other}}", "false"),
+        (r"{{ne list other}}", "true"),
+        (r"{{eq list list}}", "true"),
+        (r"{{eq one two}}", "false"),
+        (r"{{ne one two}}", "true"),
+        (r"{{eq list same}}", "true"),
+        (r"{{eq list reversed}}", "false"),
+        (r"{{eq obj obj}}", "true"),
+        (r"{{eq obj other_obj}}", "false"),
+        (r"{{gt list other}}", "false"),
+        (r"{{lt list more}}", "true"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn cmp_mixed_types() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{gt "a" list}}"#;
+    let data = json!({"list": []});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting type assertion error"),
+        Err(e) => assert_eq!(
+            "Helper 'gt' type assertion failed, expected 'string' but got 'array' at comparison.rs:1",
+            e.to_string()
+        ),
+    }
+    let value = r#"{{gt missing 1}}"#;
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting type assertion error"),
+        Err(e) => assert_eq!(
+            "Helper 'gt' type assertion failed, expected 'number|string|array' but got 'null' at comparison.rs:1",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn cmp_eq_strict() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"flag": true, "missing": null, "obj": {"a": 1}});
+    let cases = [
+        (r#"{{eq "1" 1}}"#, "false"),
+        (r#"{{ne "1" 1}}"#, "true"),
+        (r#"{{eq "true" flag}}"#, "false"),
+        (r"{{eq flag true}}", "true"),
+        (r"{{eq missing null}}", "true"),
+        (r"{{eq obj obj}}", "true"),
+        (r#"{{eq "1" 1 strict=true}}"#, "false"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn cmp_eq_loose() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"flag": true, "count": 2});
+    let cases = [
+        (r#"{{eq "1" 1 strict=false}}"#, "true"),
+        (r#"{{eq 1 "1" strict=false}}"#, "true"),
+        (r#"{{eq "1.0" 1 strict=false}}"#, "true"),
+        (r#"{{eq count "3" strict=false}}"#, "false"),
+        (r#"{{eq " 2" count strict=false}}"#, "false"),
+        (r#"{{eq "two" count strict=false}}"#, "false"),
+        (r#"{{ne "2" count strict=false}}"#, "false"),
+        (r#"{{eq flag "true" strict=false}}"#, "true"),
+        (r#"{{eq "false" flag strict=false}}"#, "false"),
+        (r#"{{eq flag "yes" strict=false}}"#, "false"),
+        (r#"{{eq "a" "a" strict=false}}"#, "true"),
+        (r#"{{#if (eq "1" 1 strict=false)}}bar{{/if}}"#, "bar"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn cmp_eq_variadic() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"x": 2, "name": "b"});
+    let cases = [
+        (r"{{eq x 1 2 3}}", "true"),
+        (r"{{eq x 1 3 5}}", "false"),
+        (r#"{{eq name "a" "b" "c"}}"#, "true"),
+        (r#"{{eq name "a" 2 null}}"#, "false"),
+        (r"{{ne x 1 3 5}}", "true"),
+        (r"{{ne x 1 2 3}}", "false"),
+        (r#"{{eq x "1" "2" strict=false}}"#, "true"),
+        (r"{{#if (eq x 1 2 3)}}bar{{/if}}", "bar"),
+        (r"{{#if (ne x 1 2 3)}}bar{{else}}qux{{/if}}", "qux"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+
+    let value = r"{{eq x}}";
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting arity error"),
+        Err(e) => assert!(e.to_string().contains("'eq'"), "{}", e),
+    }
+    Ok(())
+}
@@ -93,3 +93,105 @@ fn if_not_block() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn truthy_falsy() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        r#"{{truthy ""}} {{truthy 0}} {{truthy "a"}} {{falsy a}} {{falsy b}}"#;
+    let data = json!({"a": null, "b": []});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("false false true true false", &result);
+    Ok(())
+}
+
+#[test]
+fn if_truthy_block() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if (or (truthy a) (truthy b))}}{{foo}}{{/if}}";
+    let data = json!({"a": 0, "b": "x", "foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("bar", &result);
+    Ok(())
+}
+
+#[test]
+fn if_include_zero() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"count": 0});
+    let cases = [
+        (r"{{#if count}}foo{{else}}bar{{/if}}", "bar"),
+        (
+            r"{{#if count includeZero=true}}foo{{else}}bar{{/if}}",
+            "foo",
+        ),
+        (
+            r"{{#if missing includeZero=true}}foo{{else}}bar{{/if}}",
+            "bar",
+        ),
+        (r"{{if count includeZero=true}}", "true"),
+        (r"{{#unless count}}foo{{/unless}}", "foo"),
+        (r"{{#unless count includeZero=true}}foo{{/unless}}", ""),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn if_literal_truthiness() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    let cases = [
+        ("true", "yes"),
+        ("false", "no"),
+        ("null", "no"),
+        ("0", "no"),
+        ("0.0", "no"),
+        ("1", "yes"),
+        ("-1", "yes"),
+        ("0.5", "yes"),
+        (r#""""#, "no"),
+        (r#""0""#, "yes"),
+        (r#""false""#, "yes"),
+        ("(eq 1 1)", "yes"),
+    ];
+    for (condition, expected) in cases.iter() {
+        let value =
+            format!("{{{{#if {}}}}}yes{{{{else}}}}no{{{{/if}}}}", condition);
+        let result = registry.once(NAME, &value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn if_variable_truthiness() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "flag": true,
+        "off": false,
+        "zero": 0,
+        "empty": "",
+        "list": [],
+        "map": {},
+    });
+    let cases = [
+        ("flag", "yes"),
+        ("off", "no"),
+        ("zero", "no"),
+        ("empty", "no"),
+        ("list", "yes"),
+        ("map", "yes"),
+        ("missing", "no"),
+    ];
+    for (condition, expected) in cases.iter() {
+        let value =
+            format!("{{{{#if {}}}}}yes{{{{else}}}}no{{{{/if}}}}", condition);
+        let result = registry.once(NAME, &value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use bracket::{Registry, Result};
+use bracket::{error::RenderError, Error, Registry, Result};
 use serde_json::json;
 
 const NAME: &str = "defaults.rs";
@@ -46,3 +46,68 @@ fn defaults_block_strict() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn defaults_statement_strict_arguments() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = r#"foo{{qux "a" b=1}}bar"#;
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting missing helper error in strict mode."),
+        Err(e) => assert_eq!(
+            Error::Render(RenderError::HelperNotFound("qux".to_string())),
+            e
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn defaults_statement_strict_variable() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = r"foo{{qux}}bar";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting missing variable error in strict mode."),
+        Err(e) => assert_eq!(
+            Error::Render(RenderError::VariableNotFound(
+                "qux".to_string(),
+                NAME.to_string()
+            )),
+            e
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn defaults_parent_scope_out_of_range() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo}}[{{../../../bar}}]{{/each}}";
+    let data = json!({"foo": [1], "bar": "qux"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[]", &result);
+    Ok(())
+}
+
+#[test]
+fn defaults_parent_scope_out_of_range_strict() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = r"{{../../bar}}";
+    let data = json!({"bar": "qux"});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting parent scope error in strict mode."),
+        Err(e) => assert_eq!(
+            Error::Render(RenderError::ParentScopeOutOfRange(
+                "../../bar".to_string(),
+                2,
+                0,
+            )),
+            e
+        ),
+    }
+    Ok(())
+}
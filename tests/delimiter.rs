@@ -0,0 +1,78 @@
+use bracket::{error::Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "delimiter.rs";
+
+#[test]
+fn delimiter_statement() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    let value = r"\textbf{{<% foo %>}} {{bar}}";
+    let data = json!({"foo": "qux", "bar": "baz"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r"\textbf{{qux}} {{bar}}", &result);
+    Ok(())
+}
+
+#[test]
+fn delimiter_block() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    let value = "<%#each items~%>\n{<%this%>}<%/each%>";
+    let data = json!({"items": ["a", "b"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("{a}{b}", &result);
+    Ok(())
+}
+
+#[test]
+fn delimiter_raw() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    registry.set_escape(Box::new(bracket::escape::noop));
+    let value =
+        r"<%{foo}%> \<%foo%> <%!-- <%foo%> --%><%{{raw}}%><%foo%><%{{/raw}}%>";
+    let data = json!({"foo": "<qux>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<qux> <%foo%> <%foo%>", &result);
+    Ok(())
+}
+
+#[test]
+fn delimiter_partial() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    registry.insert("foo", "{<% bar %>}")?;
+    let value = r"<%> foo%>";
+    let data = json!({"bar": "qux"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("{qux}", &result);
+    Ok(())
+}
+
+#[test]
+fn delimiter_syntax_error_line() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    let value = "{{foo}}\n<%%>";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Identifier error expected (empty statement)"),
+        Err(e) => {
+            assert!(format!("{:?}", e).contains("delimiter.rs:2:"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn delimiter_invalid() -> Result<()> {
+    let mut registry = Registry::new();
+    match registry.set_delimiters("<", ">") {
+        Ok(_) => panic!("Expecting invalid delimiters error"),
+        Err(e) => assert_eq!(
+            Error::InvalidDelimiters("<".to_string(), ">".to_string()),
+            e
+        ),
+    }
+    Ok(())
+}
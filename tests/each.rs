@@ -1,4 +1,4 @@
-use bracket::{Registry, Result};
+use bracket::{error::HelperError, Error, Registry, Result};
 use serde_json::json;
 
 const NAME: &str = "each.rs";
@@ -42,3 +42,265 @@ fn each_map_key() -> Result<()> {
     assert_eq!("barbuz", &result);
     Ok(())
 }
+
+#[test]
+fn each_array_reverse() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo reverse=true}}{{this}}{{@index}}{{/each}}";
+    let data = json!({"foo": ["b", "a", "r"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("r0a1b2", &result);
+    Ok(())
+}
+
+#[test]
+fn each_array_limit() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo limit=2}}{{this}}{{#if @last}}!{{/if}}{{/each}}";
+    let data = json!({"foo": ["b", "a", "r"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("ba!", &result);
+    Ok(())
+}
+
+#[test]
+fn each_array_reverse_limit() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo reverse=true limit=2}}{{this}}{{/each}}";
+    let data = json!({"foo": ["b", "a", "r"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("ra", &result);
+    Ok(())
+}
+
+#[test]
+fn each_map_reverse() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo reverse=true}}{{@key}}{{/each}}";
+    let data = json!({"foo": {"bar": "baz", "buz": "qux"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("buzbar", &result);
+    Ok(())
+}
+
+#[test]
+fn each_limit_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo limit=-1}}{{this}}{{/each}}";
+    let data = json!({"foo": ["b", "a", "r"]});
+    assert!(registry.once(NAME, value, &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn each_else_empty_array() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo}}{{this}}{{else}}No items{{/each}}";
+    let data = json!({"foo": []});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("No items", &result);
+    Ok(())
+}
+
+#[test]
+fn each_else_empty_map() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo}}{{this}}{{else}}No items{{/each}}";
+    let data = json!({"foo": {}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("No items", &result);
+    Ok(())
+}
+
+#[test]
+fn each_else_missing() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo}}{{this}}{{else}}No items{{/each}}";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("No items", &result);
+    Ok(())
+}
+
+#[test]
+fn each_else_not_empty() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo}}{{this}}{{else}}No items{{/each}}";
+    let data = json!({"foo": ["a", "b"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("ab", &result);
+    Ok(())
+}
+
+#[test]
+fn each_level() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each list}}{{@level}}{{#each this}}{{@level}}{{#each this}}{{@level}}{{/each}}{{/each}}{{/each}}";
+    let data = json!({"list": [[["a"]]]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("123", &result);
+    Ok(())
+}
+
+#[test]
+fn each_level_with() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        r"{{#with foo}}{{@level}}{{#each list}}{{@level}}{{/each}}{{/with}}";
+    let data = json!({"foo": {"list": [1, 2]}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("122", &result);
+    Ok(())
+}
+
+#[test]
+fn each_level_if() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"list": [1]});
+    let cases = [
+        (r"{{@level}}", "0"),
+        (r"{{#if true}}{{@level}}{{/if}}", "1"),
+        (r"{{#if false}}{{else}}{{@level}}{{/if}}", "1"),
+        (
+            r"{{#unless false}}{{#if true}}{{@level}}{{/if}}{{/unless}}",
+            "2",
+        ),
+        (r"{{#each list}}{{#if true}}{{@level}}{{/if}}{{/each}}", "2"),
+        (r"{{#if true}}{{/if}}{{@level}}", "0"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn each_block_params() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "items": [{"name": "a"}, {"name": "b"}],
+        "map": {"x": 1, "y": 2},
+        "name": "root",
+    });
+    let cases = [
+        (r"{{#each items as |item|}}{{item.name}}{{/each}}", "ab"),
+        (
+            r"{{#each items as |item index|}}{{index}}:{{item.name}} {{/each}}",
+            "0:a 1:b ",
+        ),
+        (
+            r"{{#each map as |value key|}}{{key}}={{value}};{{/each}}",
+            "x=1;y=2;",
+        ),
+        (
+            r#"{{#each items as |item|}}{{lookup item "name"}}{{/each}}"#,
+            "ab",
+        ),
+        (
+            r#"{{#each items as |item|}}{{#if (eq item.name "b")}}{{item.name}}{{/if}}{{/each}}"#,
+            "b",
+        ),
+        (
+            r"{{#each items as |item|}}{{#each ../items as |other|}}{{item.name}}{{other.name}} {{/each}}{{/each}}",
+            "aa ab ba bb ",
+        ),
+        (
+            r"{{#each items as |name|}}{{name.name}}{{/each}}{{name}}",
+            "abroot",
+        ),
+        (
+            "{{#each items\n  as | item |\n}}{{item.name}}{{/each}}",
+            "ab",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn each_sort() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "map": {"c": 2, "a": 3, "b": 1},
+        "mixed": {"c": "x", "a": 1, "b": null, "d": 1, "e": true},
+        "list": [3, 1, 2],
+        "large": {"a": 9007199254740993u64, "b": 9007199254740992u64}
+    });
+    // Without sorting the key order depends upon the map implementation
+    let unsorted = if cfg!(feature = "preserve_order") {
+        "cab"
+    } else {
+        "abc"
+    };
+    let cases = [
+        (r"{{#each map}}{{@key}}{{/each}}", unsorted),
+        (
+            r#"{{#each map sort="key"}}{{@key}}={{this}};{{/each}}"#,
+            "a=3;b=1;c=2;",
+        ),
+        (
+            r#"{{#each map sort="value"}}{{@key}}={{this}};{{/each}}"#,
+            "b=1;c=2;a=3;",
+        ),
+        (
+            r#"{{#each map sort="key" reverse=true}}{{@key}}{{/each}}"#,
+            "cba",
+        ),
+        (
+            r#"{{#each map sort="value" limit=2}}{{@key}}{{/each}}"#,
+            "bc",
+        ),
+        (
+            r#"{{#each map sort="key"}}{{@first}}{{/each}}"#,
+            "truefalsefalse",
+        ),
+        (r#"{{#each mixed sort="value"}}{{@key}}{{/each}}"#, "beadc"),
+        (r#"{{#each list sort="key"}}{{this}}{{/each}}"#, "312"),
+        (r#"{{#each list sort="value"}}{{this}}{{/each}}"#, "123"),
+        (r#"{{#each large sort="value"}}{{@key}}{{/each}}"#, "ba"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn each_sort_err() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"map": {"a": [1], "b": 2}, "list": [[1], 2]});
+    let invalid = || {
+        HelperError::ParameterValue(
+            "each".to_string(),
+            "sort".to_string(),
+            "'key' or 'value'".to_string(),
+        )
+    };
+    let cases = [
+        (
+            r#"{{#each map sort="value"}}{{/each}}"#,
+            HelperError::SortScalarExpected("each".to_string()),
+        ),
+        (
+            r#"{{#each list sort="value"}}{{/each}}"#,
+            HelperError::SortScalarExpected("each".to_string()),
+        ),
+        (r#"{{#each map sort="size"}}{{/each}}"#, invalid()),
+        (r#"{{#each list sort="size"}}{{/each}}"#, invalid()),
+        (r#"{{#each missing sort="size"}}{{/each}}"#, invalid()),
+    ];
+    for (value, expected) in cases.iter() {
+        match registry.once(NAME, value, &data) {
+            Err(Error::Render(e)) => {
+                assert_eq!(expected.to_string(), e.to_string(), "{}", value)
+            }
+            _ => panic!("expected render error for {}", value),
+        }
+    }
+    Ok(())
+}
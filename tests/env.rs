@@ -0,0 +1,44 @@
+#![cfg(feature = "env-helper")]
+use bracket::{helper::env::Env, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "env.rs";
+
+#[test]
+fn env_read() -> Result<()> {
+    std::env::set_var("BRACKET_ENV_TEST", "foo");
+    let mut registry = Registry::new();
+    assert!(!registry.helpers().contains("env"));
+    registry.helpers_mut().insert("env", Box::new(Env::new()));
+    let data = json!({});
+    let cases = [
+        (r#"{{env "BRACKET_ENV_TEST"}}"#, "foo"),
+        (r#"{{env "BRACKET_ENV_MISSING"}}"#, "null"),
+        (
+            r#"{{#if (env "BRACKET_ENV_MISSING")}}yes{{else}}no{{/if}}"#,
+            "no",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    assert!(registry.once(NAME, r"{{env 1}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn env_allowed() -> Result<()> {
+    std::env::set_var("BRACKET_ENV_ALLOWED", "foo");
+    std::env::set_var("BRACKET_ENV_SECRET", "bar");
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert(
+        "env",
+        Box::new(Env::with_allowed(vec!["BRACKET_ENV_ALLOWED"])),
+    );
+    let data = json!({});
+    let value = r#"{{env "BRACKET_ENV_ALLOWED"}}:{{env "BRACKET_ENV_SECRET"}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("foo:null", &result);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use bracket::{escape, escape::EscapeFn, Registry, Result};
+use serde_json::json;
+use std::collections::HashMap;
+
+const NAME: &str = "escape.rs";
+
+#[test]
+fn escape_html_entities() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_escape(Box::new(escape::html_entities));
+    let value = r"{{foo}}";
+    let data = json!({"foo": "<café & 😀>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("&lt;caf&#233; &amp; &#128512;&gt;", &result);
+    Ok(())
+}
+
+#[test]
+fn escape_javascript() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_escape(Box::new(escape::javascript));
+    let value = r#"<script>var s = "{{foo}}";</script>"#;
+    let data = json!({"foo": "a\\b\"c'd\n</script>\u{2028}"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<script>var s = "a\\b\"c\'d\n\u003C/script\u003E\u2028";</script>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn escape_by_extension() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut escapes: HashMap<String, EscapeFn> = HashMap::new();
+    escapes.insert("txt".to_string(), Box::new(escape::noop));
+    registry.set_escape_by_extension(escapes);
+    registry.read_dir("tests/fixtures/escape", "txt")?;
+    registry.add("page".to_string(), "tests/fixtures/escape/page.html")?;
+    registry.insert("inline", "{{value}}")?;
+
+    let data = json!({"value": "<b>"});
+    assert_eq!(Some("txt"), registry.get("note").unwrap().extension());
+    assert_eq!(None, registry.get("page").unwrap().extension());
+    assert_eq!("<b>\n", registry.render("note", &data)?);
+    let partials = HashMap::new();
+    let result = registry.render_with_partials("note", &data, &partials)?;
+    assert_eq!("<b>\n", result);
+    assert_eq!("<p>&lt;b&gt;</p><b>\n\n", registry.render("page", &data)?);
+    assert_eq!("&lt;b&gt;", registry.render("inline", &data)?);
+    Ok(())
+}
@@ -1,10 +1,11 @@
 use bracket::{
+    error::RenderError,
     helper::*,
     parser::ast::Node,
     render::{Context, Render},
-    Registry, Result,
+    Error, Registry, Result,
 };
-use serde_json::json;
+use serde_json::{json, Value};
 
 const NAME: &str = "evaluate.rs";
 
@@ -34,3 +35,56 @@ fn helper_evaluate_path() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn registry_eval() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"score": 12, "foo": {"bar": [1, 2]}});
+    assert_eq!(Value::Bool(true), registry.eval("(gt score 10)", &data)?);
+    assert_eq!(Value::Bool(false), registry.eval("lt score 10", &data)?);
+    assert_eq!(json!([1, 2]), registry.eval("foo.bar", &data)?);
+    assert_eq!(Value::Null, registry.eval("qux", &data)?);
+    Ok(())
+}
+
+#[test]
+fn registry_eval_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    match registry.eval("foo}} {{bar", &data) {
+        Ok(_) => panic!("Expecting invalid expression error"),
+        Err(e) => assert_eq!(
+            Error::Render(RenderError::InvalidExpression(
+                "foo}} {{bar".to_string()
+            )),
+            e
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn render_single_value() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("config", "{{json settings}}\n")?;
+    registry.insert("items", "{{items}}")?;
+    let data = json!({"settings": {"debug": true}, "items": [1, 2, 3]});
+    let value = registry.render_single_value("config", &data)?;
+    assert_eq!(Some(Value::String(r#"{"debug":true}"#.to_string())), value);
+    let value = registry.render_single_value("items", &data)?;
+    assert_eq!(Some(json!([1, 2, 3])), value);
+    Ok(())
+}
+
+#[test]
+fn render_single_value_invalid() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("multiple", "{{foo}} {{bar}}")?;
+    let data = json!({});
+    let result = registry.render_single_value("multiple", &data);
+    assert_eq!(
+        Error::Render(RenderError::SingleExpression("multiple".to_string())),
+        result.err().unwrap()
+    );
+    Ok(())
+}
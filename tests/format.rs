@@ -0,0 +1,167 @@
+use bracket::{parser::FormatOptions, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "format.rs";
+
+#[test]
+fn format_statement() -> Result<()> {
+    let registry = Registry::new();
+    let options: FormatOptions = Default::default();
+    let cases = [
+        ("{{  foo  }}", "{{foo}}"),
+        ("{{~foo ~}}", "{{~foo~}}"),
+        ("{{{ foo }}}", "{{{foo}}}"),
+        ("{{foo/bar}}", "{{foo.bar}}"),
+        ("{{../foo}}", "{{../foo}}"),
+        ("{{./foo}}", "{{./foo}}"),
+        ("{{foo.[bar baz]}}", "{{foo.[bar baz]}}"),
+        ("{{join  list   \", \"}}", "{{join list \", \"}}"),
+        ("{{foo  bar=1   baz=(qux  1)}}", "{{foo bar=1 baz=(qux 1)}}"),
+        ("{{>  header  title=\"x\"}}", "{{> header title=\"x\"}}"),
+        ("{{> (name)}}", "{{> (name)}}"),
+        ("{{! comment }}", "{{! comment }}"),
+        ("{{!--  comment  --}}", "{{!--  comment  --}}"),
+        ("\\{{ raw }}", "\\{{ raw }}"),
+    ];
+    for (value, expected) in cases.iter() {
+        let template = registry.parse(NAME, value)?;
+        assert_eq!(expected, &template.format(options.clone()));
+    }
+    Ok(())
+}
+
+#[test]
+fn format_padding() -> Result<()> {
+    let registry = Registry::new();
+    let options = FormatOptions {
+        padding: true,
+        ..Default::default()
+    };
+    let cases = [
+        ("{{foo}}", "{{ foo }}"),
+        ("{{~foo ~}}", "{{~foo~}}"),
+        ("{{> foo}}", "{{> foo }}"),
+        ("{{#if foo}}{{else}}{{/if}}", "{{#if foo}}{{else}}{{/if}}"),
+    ];
+    for (value, expected) in cases.iter() {
+        let template = registry.parse(NAME, value)?;
+        assert_eq!(expected, &template.format(options.clone()));
+    }
+    Ok(())
+}
+
+#[test]
+fn format_block() -> Result<()> {
+    let registry = Registry::new();
+    let options: FormatOptions = Default::default();
+    let cases = [
+        (
+            "{{# if  foo ~}}a{{~ else  if bar}}b{{else}}c{{~ /if }}",
+            "{{#if foo~}}a{{~else if bar}}b{{else}}c{{~/if}}",
+        ),
+        ("{{#>  layout}}x{{/layout}}", "{{#> layout}}x{{/layout}}"),
+        (
+            "{{#*inline  \"foo\"}}x{{/inline}}",
+            "{{#*inline \"foo\"}}x{{/inline}}",
+        ),
+        (
+            "{{{{raw}}}} {{foo}} {{{{/raw}}}}",
+            "{{{{raw}}}} {{foo}} {{{{/raw}}}}",
+        ),
+        (
+            "{{#each  list  as | item  index |}}x{{/each}}",
+            "{{#each list as |item index|}}x{{/each}}",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let template = registry.parse(NAME, value)?;
+        assert_eq!(expected, &template.format(options.clone()));
+    }
+    Ok(())
+}
+
+#[test]
+fn format_indent() -> Result<()> {
+    let registry = Registry::new();
+    let options = FormatOptions {
+        indent: Some(2),
+        ..Default::default()
+    };
+    let value = r#"<ul>
+    {{#each list}}
+{{#if this}}
+        <li>{{this}}</li>
+
+      {{else}}
+  <li>none</li>
+            {{/if}}
+ {{/each}}
+</ul>"#;
+    let expected = r#"<ul>
+    {{#each list}}
+      {{#if this}}
+        <li>{{this}}</li>
+
+      {{else}}
+        <li>none</li>
+      {{/if}}
+    {{/each}}
+</ul>"#;
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(expected, &template.format(options));
+    Ok(())
+}
+
+#[test]
+fn format_render() -> Result<()> {
+    let registry = Registry::new();
+    let options = FormatOptions {
+        padding: true,
+        indent: Some(4),
+    };
+    let value = r#"{{#each  list~}}
+  {{#if  on}}<{{name}}>{{~else~}}
+[{{name}}]{{/if}}
+{{~/each}}"#;
+    let data = json!({"list": [
+        {"name": "foo", "on": true},
+        {"name": "bar", "on": false},
+    ]});
+    let template = registry.parse(NAME, value)?;
+    let formatted = template.format(options);
+    let expected = registry.once(NAME, value, &data)?;
+    let result = registry.once(NAME, &formatted, &data)?;
+    assert_eq!("<foo>[bar]", &expected);
+    assert_eq!(expected, result);
+    Ok(())
+}
+
+#[test]
+fn format_line_comment() -> Result<()> {
+    let registry = Registry::new();
+    let options: FormatOptions = Default::default();
+    let cases = [
+        ("{{foo ; note}}", "{{foo ; note}}"),
+        (
+            "{{foo  bar ; note\n  baz=1 ; other\n}}",
+            "{{foo bar baz=1 ; note ; other}}",
+        ),
+        ("{{eq (len list ; count) 2}}", "{{eq (len list) 2 ; count}}"),
+        ("{{foo ; trim ~}}", "{{foo ; trim~}}"),
+        (
+            "{{#if  foo ; check\n}}a{{else ; otherwise\n}}b{{/if}}",
+            "{{#if foo ; check}}a{{else ; otherwise}}b{{/if}}",
+        ),
+        (r#"{{json "a;b"}}"#, r#"{{json "a;b"}}"#),
+    ];
+    for (value, expected) in cases.iter() {
+        let template = registry.parse(NAME, value)?;
+        let formatted = template.format(options.clone());
+        assert_eq!(expected, &formatted, "{}", value);
+
+        // Formatting the formatted template is stable
+        let template = registry.parse(NAME, &formatted)?;
+        assert_eq!(formatted, template.format(options.clone()), "{}", value);
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use bracket::{helper::prelude::*, Registry, Result};
+use bracket::{
+    error::RenderError, helper::prelude::*, registry::NonFinite, Registry,
+    Result,
+};
 use serde_json::{json, Value};
 
 const NAME: &str = "helper.rs";
@@ -37,6 +40,165 @@ impl Helper for FooBlockHelper {
     }
 }
 
+pub struct SourceHelper;
+impl Helper for SourceHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let source = ctx.argument_source(0).unwrap_or("");
+        Ok(Some(Value::String(source.to_string())))
+    }
+}
+
+pub struct AttrsHelper;
+impl Helper for AttrsHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        for key in ctx.hash_keys() {
+            let value = ctx.param(key).and_then(|v| v.as_str()).unwrap_or("");
+            rc.write(&format!(r#" {}="{}""#, key, value))?;
+        }
+        Ok(None)
+    }
+}
+
+pub struct FailHelper;
+impl Helper for FailHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        match ctx.get(0).and_then(|v| v.as_str()) {
+            Some(value) => {
+                Err(helper_error!("Helper '{}' got '{}'", ctx.name(), value))
+            }
+            None => ctx.fail("Argument is required"),
+        }
+    }
+}
+
+pub struct PairHelper;
+impl Helper for PairHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+        Ok(Some(Value::String(ctx.name().to_string())))
+    }
+}
+
+pub struct TraceHelper<'reg> {
+    inner: Box<dyn Helper + 'reg>,
+}
+impl Helper for TraceHelper<'_> {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        rc.write(&format!("[{}]", ctx.name()))?;
+        self.inner.call(rc, ctx, template)
+    }
+}
+
+pub struct InvocationHelper;
+impl Helper for InvocationHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        rc.write(&format!(
+            "{:?}:{}:{}",
+            ctx.invocation(),
+            ctx.is_block(),
+            ctx.is_raw()
+        ))?;
+        Ok(None)
+    }
+}
+
+pub struct HeadingHelper;
+impl Helper for HeadingHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let title = ctx.try_get(0, &[Type::String])?.clone();
+        let mut headings = rc
+            .get_render_data("headings")
+            .unwrap_or_else(|| Value::Array(Vec::new()));
+        if let Value::Array(ref mut list) = headings {
+            list.push(title.clone());
+        }
+        rc.set_render_data("headings", headings);
+        Ok(Some(title))
+    }
+}
+
+pub struct TocHelper;
+impl Helper for TocHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let content = rc.buffer(ctx.assert_block(template)?)?;
+        if let Some(Value::Array(headings)) = rc.get_render_data("headings") {
+            for heading in headings {
+                rc.write(&format!("[{}]", heading.as_str().unwrap()))?;
+            }
+        }
+        rc.write(&content)?;
+        Ok(None)
+    }
+}
+
+pub struct CacheHelper;
+impl Helper for CacheHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        rc.write_raw(b"  <p>cached</p>  ")?;
+        Ok(None)
+    }
+}
+
+pub struct WrapHelper;
+impl Helper for WrapHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..2)?;
+        let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let content = rc.render_partial_to_string(name, ctx.get(1).cloned())?;
+        Ok(Some(Value::String(format!("[{}]", content.to_uppercase()))))
+    }
+}
+
 pub struct HelperMissing;
 impl Helper for HelperMissing {
     fn call<'render, 'call>(
@@ -50,6 +212,89 @@ impl Helper for HelperMissing {
     }
 }
 
+pub struct SumHelper;
+impl Helper for SumHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let sum: f64 = ctx
+            .try_get_all(&[Type::Number])?
+            .iter()
+            .fold(0.0, |sum, v| sum + v.as_f64().unwrap());
+        Ok(Some(Value::from(sum)))
+    }
+}
+
+pub struct DivideHelper;
+impl Helper for DivideHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+        let lhs = ctx.try_get(0, &[Type::Number])?.as_f64().unwrap();
+        let rhs = ctx.try_get(1, &[Type::Number])?.as_f64().unwrap();
+        Ok(Some(rc.float(lhs / rhs)))
+    }
+}
+
+pub struct ExpandHelper;
+impl Helper for ExpandHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        // Rewrite `[[` and `]]` in the block source as
+        // statements then render the result
+        let source = rc.block_source(ctx.assert_block(template)?);
+        let source = source.replace("[[", "{{").replace("]]", "}}");
+        let data = rc.data().clone();
+        let result = rc.once(NAME, &source, &data)?;
+        rc.write(&result)?;
+        Ok(None)
+    }
+}
+
+pub struct BlockSourceHelper;
+impl Helper for BlockSourceHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let source = rc.block_source(ctx.assert_block(template)?);
+        rc.write(source)?;
+        Ok(None)
+    }
+}
+
+pub struct WhenHelper;
+impl Helper for WhenHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let template = ctx.assert_block(template)?;
+        if ctx.is_truthy(ctx.get(0).unwrap()) {
+            rc.template(template)?;
+        } else if !rc.render_inverse(template)? {
+            rc.write("none")?;
+        }
+        Ok(None)
+    }
+}
+
 pub struct BlockHelperMissing;
 impl Helper for BlockHelperMissing {
     fn call<'render, 'call>(
@@ -140,3 +385,382 @@ fn helper_block_missing() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn helper_argument_source() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_escape(Box::new(bracket::escape::noop));
+    registry
+        .helpers_mut()
+        .insert("source", Box::new(SourceHelper {}));
+    let data = json!({"foo": "qux"});
+    let result = registry.once(NAME, r#"{{source "foo"}}"#, &data)?;
+    assert_eq!(r#""foo""#, &result);
+    let result = registry.once(NAME, r"{{source foo.bar}}", &data)?;
+    assert_eq!("foo.bar", &result);
+    let result = registry.once(NAME, r"{{source (json foo)}}", &data)?;
+    assert_eq!("(json foo)", &result);
+    Ok(())
+}
+
+#[test]
+fn helper_insert_shadowed() -> Result<()> {
+    let mut registry = Registry::new();
+    assert!(registry
+        .helpers_mut()
+        .insert("foo", Box::new(FooHelper {}))
+        .is_none());
+    assert!(registry
+        .helpers_mut()
+        .insert("foo", Box::new(FooHelper {}))
+        .is_some());
+    Ok(())
+}
+
+#[test]
+fn helper_hash_keys() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("attrs", Box::new(AttrsHelper {}));
+    let data = json!({});
+    let result =
+        registry.once(NAME, r#"<p{{attrs id="foo" class="bar"}}>"#, &data)?;
+    assert_eq!(r#"<p id="foo" class="bar">"#, &result);
+    Ok(())
+}
+
+#[test]
+fn helper_fail() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("fail", Box::new(FailHelper {}));
+    let data = json!({});
+    let result = registry.once(NAME, r"{{fail}}", &data);
+    assert_eq!("Argument is required", result.err().unwrap().to_string());
+    let result = registry.once(NAME, r#"{{fail "foo"}}"#, &data);
+    assert_eq!("Helper 'fail' got 'foo'", result.err().unwrap().to_string());
+    Ok(())
+}
+
+#[test]
+fn helper_invoked_name() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("pair", Box::new(PairHelper {}));
+    registry
+        .helpers_mut()
+        .insert("couple", Box::new(PairHelper {}));
+    registry
+        .helpers_mut()
+        .insert("equals", Box::new(bracket::helper::comparison::Equal {}));
+    let data = json!({});
+    let result = registry.once(NAME, r"{{pair 1 2}}{{couple 1 2}}", &data)?;
+    assert_eq!("paircouple", &result);
+    let cases = [
+        (r"{{pair 1}}", "pair", "2 arguments(s)"),
+        (r"{{couple 1}}", "couple", "2 arguments(s)"),
+        (r"{{equals 1}}", "equals", "at least 2 argument(s)"),
+        (r"{{eq 1}}", "eq", "at least 2 argument(s)"),
+    ];
+    for (value, name, expects) in cases.iter() {
+        let result = registry.once(NAME, value, &data);
+        assert_eq!(
+            format!("Helper '{}' got invalid arity expects {}", name, expects),
+            result.err().unwrap().to_string()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_take() -> Result<()> {
+    let mut registry = Registry::new();
+    assert!(registry.take_helper("foo").is_none());
+    let inner = registry.take_helper("eq").unwrap();
+    assert!(!registry.helpers().contains("eq"));
+    registry
+        .helpers_mut()
+        .insert("eq", Box::new(TraceHelper { inner }));
+    let data = json!({});
+    let result = registry.once(NAME, r"{{eq 1 1}}", &data)?;
+    assert_eq!("[eq]true", &result);
+    Ok(())
+}
+
+#[test]
+fn helper_names() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("foo", Box::new(FooHelper {}));
+    assert!(registry.helpers().contains("foo"));
+    assert!(!registry.helpers().contains("qux"));
+    let names: Vec<&str> = registry.helper_names().collect();
+    assert!(names.contains(&"foo"));
+    assert!(names.contains(&"if"));
+    Ok(())
+}
+
+#[test]
+fn helper_invocation() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("invocation", Box::new(InvocationHelper {}));
+    let data = json!({});
+    let cases = [
+        (r"{{invocation}}", "Statement:false:false"),
+        (r"{{#invocation}}{{/invocation}}", "Block:true:false"),
+        (r"{{{{invocation}}}}{{{{/invocation}}}}", "Raw:true:true"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_render_data() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("heading", Box::new(HeadingHelper {}));
+    registry.helpers_mut().insert("toc", Box::new(TocHelper {}));
+    let value = r#"{{#toc}}{{#each list}}<{{heading this}}>{{/each}}{{/toc}}"#;
+    let data = json!({"list": ["foo", "bar"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[foo][bar]<foo><bar>", &result);
+    Ok(())
+}
+
+#[test]
+fn helper_write_raw() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("cache", Box::new(CacheHelper {}));
+    let value = r#"<div>{{#if true~}}{{cache}}{{~/if}}</div>"#;
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<div>  <p>cached</p>  </div>", &result);
+    Ok(())
+}
+
+#[test]
+fn helper_disable_builtin() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = r#"{{log "message"}}"#;
+    let data = json!({});
+    assert!(registry.disable_helper("log"));
+    assert!(!registry.disable_helper("log"));
+    assert!(registry.once(NAME, value, &data).is_err());
+    assert!(registry.enable_builtin("log"));
+    assert!(!registry.enable_builtin("foo"));
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("", &result);
+    Ok(())
+}
+
+#[test]
+fn helper_render_partial_to_string() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("card", "<b>{{title}}</b>")?;
+    registry
+        .helpers_mut()
+        .insert("wrap", Box::new(WrapHelper {}));
+    let data = json!({"title": "foo", "other": {"title": "bar"}});
+    let cases = [
+        (r#"{{{wrap "card"}}}"#, "[<B>FOO</B>]"),
+        (r#"{{{wrap "card" other}}}"#, "[<B>BAR</B>]"),
+        (
+            r#"{{#*inline "local"}}{{title}}{{/inline}}{{wrap "local"}}"#,
+            "[FOO]",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    assert!(registry.once(NAME, r#"{{wrap "missing"}}"#, &data).is_err());
+
+    registry.insert("cycle", r#"{{wrap "cycle"}}"#)?;
+    assert!(registry.once(NAME, r#"{{wrap "cycle"}}"#, &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn helper_render_partial_block() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("card", r#"<{{{wrap "@partial-block"}}}>"#)?;
+    registry.insert("toc", "{{#toc}}{{> @partial-block}}{{/toc}}")?;
+    registry.insert("layout", "({{> @partial-block}})")?;
+    registry
+        .helpers_mut()
+        .insert("wrap", Box::new(WrapHelper {}));
+    registry.helpers_mut().insert("toc", Box::new(TocHelper {}));
+    let data = json!({"title": "foo"});
+    let cases = [
+        (r"{{#> card}}<i>{{title}}</i>{{/card}}", "<[<I>FOO</I>]>"),
+        (r"{{#> toc}}{{title}}{{/toc}}", "foo"),
+        (
+            r"{{#> layout}}{{#> card}}{{title}}{{/card}}{{/layout}}",
+            "(<[FOO]>)",
+        ),
+        (
+            r"{{#> card}}{{#> layout}}{{title}}{{/layout}}{{/card}}",
+            "<[(FOO)]>",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry
+        .once(NAME, r#"{{wrap "@partial-block"}}"#, &data)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn helper_registry_builder() -> Result<()> {
+    let registry = Registry::builder()
+        .helper("foo", Box::new(FooHelper {}))
+        .strict(true)
+        .escape(Box::new(bracket::escape::noop))
+        .max_stack_depth(2)
+        .build();
+    assert!(registry.strict());
+    assert_eq!(2, registry.max_stack_depth());
+    let data = json!({"a": {"b": {"c": "<qux>"}}});
+    assert_eq!("bar", registry.once(NAME, "{{foo}}", &data)?);
+    assert_eq!("<qux>", registry.once(NAME, "{{a.b.c}}", &data)?);
+    let value = r"{{#with a}}{{#with b}}{{c}}{{/with}}{{/with}}";
+    assert_eq!("<qux>", registry.once(NAME, value, &data)?);
+    let value = r"{{#with a}}{{#with b}}{{#with c}}{{/with}}{{/with}}{{/with}}";
+    let err = registry.once(NAME, value, &data).unwrap_err();
+    assert_eq!(
+        RenderError::HelperCycle("with".to_string()).to_string(),
+        err.to_string()
+    );
+    assert!(registry.once(NAME, "{{missing}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn helper_try_get_all() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("sum", Box::new(SumHelper {}));
+    let data = json!({"n": 4});
+    assert_eq!("10.0", registry.once(NAME, "{{sum 1 2 3 n}}", &data)?);
+    assert_eq!("0.0", registry.once(NAME, "{{sum}}", &data)?);
+    let err = registry
+        .once(NAME, r#"{{sum 1 "2" 3}}"#, &data)
+        .unwrap_err();
+    assert_eq!(
+        HelperError::ArgumentType(
+            "sum".to_string(),
+            1,
+            "number".to_string(),
+            "string".to_string()
+        )
+        .to_string(),
+        err.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn helper_block_source() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("expand", Box::new(ExpandHelper {}));
+    let data = json!({"name": "foo"});
+    let cases = [
+        (r"{{#expand}}[[name]]={{name}}{{/expand}}", "foo=foo"),
+        (r"{{#expand}}[[#if name]]a{{else}}b[[/if]]{{/expand}}", "a"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_block_source_delimiters() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    registry
+        .helpers_mut()
+        .insert("source", Box::new(BlockSourceHelper {}));
+    let data = json!({"name": "foo"});
+    let cases = [
+        (r"<%#source%>{a} <%name%><%/source%>", "{a} <%name%>"),
+        (
+            r"<%#source%><%#if name%>{}<%else%>b<%/if%><%/source%>",
+            "<%#if name%>{}<%else%>b<%/if%>",
+        ),
+        (r"<%#if name%><%#source%>{a}<%/source%><%/if%>", "{a}"),
+        (r"<%#if x%>a<%else%><%#source%>}<%/source%><%/if%>", "}"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_render_inverse() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("when", Box::new(WhenHelper {}));
+    let data = json!({"yes": true, "no": false});
+    let cases = [
+        (r"{{#when yes}}a{{else}}b{{/when}}", "a"),
+        (r"{{#when no}}a{{else}}b{{/when}}", "b"),
+        (r"{{#when no}}a{{else if yes}}c{{else}}b{{/when}}", "c"),
+        (r"{{#when no}}a{{else if no}}c{{/when}}", "none"),
+        (r"{{#when no}}a{{/when}}", "none"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_float_non_finite() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("divide", Box::new(DivideHelper {}));
+    let data = json!({});
+    let cases = [
+        (r"{{divide 1 4}}", "0.25"),
+        (r"{{divide 1 0}}", ""),
+        (r"{{divide 0 0}}", ""),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+
+    registry.set_non_finite_repr(NonFinite::Name);
+    let cases = [
+        (r"{{divide 1 0}}", "Infinity"),
+        (r"{{divide -1 0}}", "-Infinity"),
+        (r"{{divide 0 0}}", "NaN"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
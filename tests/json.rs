@@ -0,0 +1,53 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "json.rs";
+
+#[test]
+fn json_compact() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{{json foo}}}";
+    let data = json!({"foo": {"a": [true], "b": 1}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"{"a":[true],"b":1}"#, &result);
+    Ok(())
+}
+
+#[test]
+fn json_escaped() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{json foo}}";
+    let data = json!({"foo": {"a": 1}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("{&quot;a&quot;:1}", &result);
+    Ok(())
+}
+
+#[test]
+fn json_indent() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{{json foo indent=4}}}";
+    let data = json!({"foo": {"a": [1]}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("{\n    \"a\": [\n        1\n    ]\n}", &result);
+    Ok(())
+}
+
+#[test]
+fn json_sort_keys() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{{json foo sort_keys=true}}}";
+    let data = json!({"foo": {"b": {"d": 1, "c": 2}, "a": [{"f": 3, "e": 4}]}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"{"a":[{"e":4,"f":3}],"b":{"c":2,"d":1}}"#, &result);
+    Ok(())
+}
+
+#[test]
+fn json_indent_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{json foo indent=true}}";
+    let data = json!({"foo": {}});
+    assert!(registry.once(NAME, value, &data).is_err());
+    Ok(())
+}
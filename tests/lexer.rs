@@ -79,11 +79,7 @@ fn lex_raw_block_text() {
         Token::Parameters(Parameters::Identifier, 5..8),
         Token::Parameters(Parameters::WhiteSpace, 8..9),
         Token::Parameters(Parameters::End, 9..13),
-        Token::Block(Block::Text, 13..17),
-        Token::Block(Block::StartStatement, 17..19),
-        Token::Parameters(Parameters::Identifier, 19..22),
-        Token::Parameters(Parameters::End, 22..24),
-        Token::Block(Block::Text, 24..28),
+        Token::Block(Block::Text, 13..28),
         Token::Block(Block::EndRawBlock, 28..34),
         Token::Parameters(Parameters::WhiteSpace, 34..35),
         Token::Parameters(Parameters::Identifier, 35..38),
@@ -106,11 +102,7 @@ baz
         Token::Block(Block::StartRawBlock, 0..4),
         Token::Parameters(Parameters::Identifier, 4..7),
         Token::Parameters(Parameters::End, 7..11),
-        Token::Block(Block::Text, 11..16),
-        Token::Block(Block::StartStatement, 16..18),
-        Token::Parameters(Parameters::Identifier, 18..21),
-        Token::Parameters(Parameters::End, 21..23),
-        Token::Block(Block::Text, 23..28),
+        Token::Block(Block::Text, 11..28),
         Token::Block(Block::EndRawBlock, 28..33),
         Token::Parameters(Parameters::Identifier, 33..36),
         Token::Parameters(Parameters::End, 36..40),
@@ -349,3 +341,37 @@ fn lex_link() {
     ];
     assert_eq!(expect, tokens);
 }
+
+#[test]
+fn lex_line_comment() {
+    let value = "{{foo ; a comment\n  bar;baz}}";
+    let tokens = lex(value, true);
+    let expect = vec![
+        Token::Block(Block::StartStatement, 0..2),
+        Token::Parameters(Parameters::Identifier, 2..5),
+        Token::Parameters(Parameters::WhiteSpace, 5..6),
+        Token::Parameters(Parameters::LineComment, 6..17),
+        Token::Parameters(Parameters::Newline, 17..18),
+        Token::Parameters(Parameters::WhiteSpace, 18..20),
+        Token::Parameters(Parameters::Identifier, 20..23),
+        Token::Parameters(Parameters::LineComment, 23..27),
+        Token::Parameters(Parameters::End, 27..29),
+    ];
+    assert_eq!(expect, tokens);
+}
+
+#[test]
+fn lex_line_comment_sub_expr() {
+    let value = "{{(foo ; a comment)}}";
+    let tokens = lex(value, true);
+    let expect = vec![
+        Token::Block(Block::StartStatement, 0..2),
+        Token::Parameters(Parameters::StartSubExpression, 2..3),
+        Token::Parameters(Parameters::Identifier, 3..6),
+        Token::Parameters(Parameters::WhiteSpace, 6..7),
+        Token::Parameters(Parameters::LineComment, 7..18),
+        Token::Parameters(Parameters::EndSubExpression, 18..19),
+        Token::Parameters(Parameters::End, 19..21),
+    ];
+    assert_eq!(expect, tokens);
+}
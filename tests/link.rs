@@ -169,3 +169,92 @@ Info</a>"#,
     );
     Ok(())
 }
+
+#[test]
+fn link_resolver() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.handlers_mut().link = Some(Box::new(LinkHelper {}));
+    registry.handlers_mut().link_resolver = Some(Box::new(|href: &str| {
+        format!("/wiki/{}", href.to_lowercase())
+    }));
+    let value = r"[[SomeTarget|Label]]";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<a href="/wiki/sometarget" title="Label">Label</a>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn link_render_labels() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_render_link_labels(true);
+    registry.handlers_mut().link = Some(Box::new(LinkHelper {}));
+    let value =
+        r"{{#with page}}[[Target|{{name}} & more|About {{name}}]]{{/with}}";
+    let data = json!({"page": {"name": "<Foo>"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<a href="Target" title="About &lt;Foo&gt;">&lt;Foo&gt; &amp; more</a>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn link_render_labels_state() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_render_link_labels(true);
+    registry.handlers_mut().link = Some(Box::new(LinkHelper {}));
+    let value = r#"{{#*inline "suffix"}}!{{/inline}}{{#each list as |item|}}[[Target|{{item}} of {{@root.title}}{{> suffix}}]]{{/each}}"#;
+    let data = json!({"title": "Foo", "list": ["a"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<a href="Target" title="a of Foo!">a of Foo!</a>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn link_render_labels_disabled() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.handlers_mut().link = Some(Box::new(LinkHelper {}));
+    let value = r"[[Target|{{name}}]]";
+    let data = json!({"name": "Foo"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"<a href="Target" title="{{name}}">{{name}}</a>"#, &result);
+    Ok(())
+}
+
+#[test]
+fn link_escaped_backslash() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.handlers_mut().link = Some(Box::new(LinkHelper {}));
+    let data = json!({});
+    let cases = [
+        (
+            r"[[url|a\|b\\c]]",
+            r#"<a href="url" title="a|b\c">a|b\c</a>"#,
+        ),
+        (
+            r"[[url|\[a\] b|c\\\|d]]",
+            r#"<a href="url" title="c\|d">[a] b</a>"#,
+        ),
+        (
+            r"[[a\\b\[c|x\\ny]]",
+            r#"<a href="a\b[c" title="x\ny">x\ny</a>"#,
+        ),
+        (
+            r"[[url|trailing\\]]",
+            r#"<a href="url" title="trailing\">trailing\</a>"#,
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use bracket::{
+    error::{Error, RenderError},
+    helper::prelude::*,
+    Registry, Result,
+};
+
+const NAME: &str = "lint.rs";
+
+pub struct MissingHelper;
+impl Helper for MissingHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        Ok(None)
+    }
+}
+
+#[test]
+fn lint_strict_syntax_error() -> Result<()> {
+    let registry = Registry::new();
+    let errors = registry.lint_strict(NAME, "{{.bad.path}}")?;
+    assert_eq!(1, errors.len());
+    assert!(matches!(errors[0], Error::Syntax(_)));
+    Ok(())
+}
+
+#[test]
+fn lint_strict_ok() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("header", "<h1>{{title}}</h1>")?;
+    let value = r#"{{#*inline "footer"}}{{year}}{{/inline}}
+{{> header}}{{> footer}}{{title}}{{#if (eq title "foo")}}{{/if}}
+{{{{raw foo}}}}{{{{/raw}}}}{{#each list}}{{this}}{{else}}{{/each}}"#;
+    let errors = registry.lint_strict(NAME, value)?;
+    assert!(errors.is_empty());
+    Ok(())
+}
+
+#[test]
+fn lint_strict_helper_not_found() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        r#"{{uppr name}}{{#if (lenght list)}}{{/if}}{{#eech list}}{{/eech}}"#;
+    let errors = registry.lint_strict(NAME, value)?;
+    let expected = vec![
+        Error::from(RenderError::HelperNotFound("uppr".to_string())),
+        Error::from(RenderError::HelperNotFound("lenght".to_string())),
+        Error::from(RenderError::HelperNotFound("eech".to_string())),
+    ];
+    assert_eq!(expected, errors);
+    Ok(())
+}
+
+#[test]
+fn lint_strict_helper_missing() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.handlers_mut().helper_missing = Some(Box::new(MissingHelper {}));
+    let errors = registry.lint_strict(NAME, "{{uppr name}}")?;
+    assert!(errors.is_empty());
+    Ok(())
+}
+
+#[test]
+fn lint_strict_partial_not_found() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{> headr}}{{#> layout}}{{/layout}}"#;
+    let errors = registry.lint_strict(NAME, value)?;
+    let expected = vec![
+        Error::from(RenderError::PartialNotFound("headr".to_string())),
+        Error::from(RenderError::PartialNotFound("layout".to_string())),
+    ];
+    assert_eq!(expected, errors);
+    Ok(())
+}
+
+#[test]
+fn lint_data_shadowed() -> Result<()> {
+    let registry = Registry::new();
+    let data = serde_json::json!({"first": "a", "title": "b", "len": 2});
+    let value = r"{{first}}{{title}}{{./len}}{{this.len}}{{len title}}{{#if first}}{{/if}}";
+    let errors = registry.lint_data(NAME, value, &data)?;
+    let expected = vec![Error::from(RenderError::HelperShadowsVariable(
+        "first".to_string(),
+    ))];
+    assert_eq!(expected, errors);
+    assert_eq!("b", registry.once(NAME, "{{./title}}", &data)?);
+    assert_eq!("2", registry.once(NAME, "{{./len}}", &data)?);
+    assert!(registry
+        .lint_data(NAME, value, &serde_json::json!([]))?
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use bracket::{helper::log::Level, Registry, Result};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+const NAME: &str = "log.rs";
+
+#[test]
+fn log_sink() -> Result<()> {
+    let messages: Arc<Mutex<Vec<(Level, String)>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&messages);
+
+    let mut registry = Registry::new();
+    registry.set_log_sink(Box::new(move |level: Level, message: &str| {
+        sink.lock().unwrap().push((level, message.to_string()));
+    }));
+
+    let value = r#"{{log "foo" bar}}{{log "qux" level="warn"}}"#;
+    let data = json!({"bar": "baz"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("", &result);
+
+    let messages = messages.lock().unwrap();
+    assert_eq!(
+        vec![
+            (Level::Info, "foo baz".to_string()),
+            (Level::Warn, "qux".to_string()),
+        ],
+        *messages
+    );
+    Ok(())
+}
+
+#[test]
+fn log_level_invalid() -> Result<()> {
+    let messages: Arc<Mutex<Vec<Level>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&messages);
+
+    let mut registry = Registry::new();
+    registry.set_log_sink(Box::new(move |level: Level, _: &str| {
+        sink.lock().unwrap().push(level);
+    }));
+
+    let value = r#"{{log "foo" level=1}}{{log "bar" level="unknown"}}"#;
+    let data = json!({});
+    registry.once(NAME, value, &data)?;
+    assert_eq!(vec![Level::Info, Level::Info], *messages.lock().unwrap());
+    Ok(())
+}
+
+#[test]
+fn log_block_params() -> Result<()> {
+    let messages: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&messages);
+
+    let mut registry = Registry::new();
+    registry.set_log_sink(Box::new(move |_: Level, message: &str| {
+        sink.lock().unwrap().push(message.to_string());
+    }));
+
+    let value = r#"{{#each items as |item|}}{{log item.name (lookup item "id") level=item.level}}{{/each}}"#;
+    let data = json!({"items": [
+        {"name": "foo", "id": 1, "level": "info"},
+        {"name": "bar", "id": 2, "level": "warn"},
+    ]});
+    registry.once(NAME, value, &data)?;
+
+    let messages = messages.lock().unwrap();
+    assert_eq!(vec!["foo 1".to_string(), "bar 2".to_string()], *messages);
+    Ok(())
+}
@@ -32,3 +32,65 @@ fn lookup_deep() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn lookup_has() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "obj": {"a": {"b": {"c": null, "d": false}}, "list": [{"e": 1}]},
+        "text": "foo"
+    });
+    let cases = [
+        (r#"{{has obj "a.b.c"}}"#, "true"),
+        (r#"{{has obj "a.b.d"}}"#, "true"),
+        (r#"{{has obj "a"}}"#, "true"),
+        (r#"{{has obj "a.x.c"}}"#, "false"),
+        (r#"{{has obj "a.b.c.d"}}"#, "false"),
+        (r#"{{has obj "list.0.e"}}"#, "true"),
+        (r#"{{has obj "list.1.e"}}"#, "false"),
+        (r#"{{has text "a"}}"#, "false"),
+        (r#"{{has missing "a"}}"#, "false"),
+        (r#"{{#if (has obj "a.b.c")}}yes{{else}}no{{/if}}"#, "yes"),
+        (r#"{{#if (has obj "a.c")}}yes{{else}}no{{/if}}"#, "no"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry.once(NAME, r"{{has obj}}", &data).is_err());
+    assert!(registry.once(NAME, r"{{has obj 1}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn lookup_parallel_arrays() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "names": ["foo", "bar", "qux"],
+        "ages": [21, 32, 43],
+        "people": {"title": "People"}
+    });
+    let cases = [
+        (
+            r"{{#each names}}{{this}}={{lookup ../ages @index}};{{/each}}",
+            "foo=21;bar=32;qux=43;",
+        ),
+        (
+            r"{{#each names as |name i|}}{{name}}={{lookup ../ages i}};{{/each}}",
+            "foo=21;bar=32;qux=43;",
+        ),
+        (
+            r"{{#each names}}{{lookup @root.ages @index}} {{/each}}",
+            "21 32 43 ",
+        ),
+        (
+            r"{{#with people}}{{#each ../names}}{{lookup ../../ages @index}} {{/each}}{{/with}}",
+            "21 32 43 ",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use bracket::{
+    error::HelperError, helper::number::MAX_PRECISION, registry::NonFinite,
+    Error, Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "number.rs";
+
+#[test]
+fn number_format() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"value": 1234567.891, "count": -1234567});
+    let cases = [
+        (r"{{number 42}}", "42"),
+        (r"{{number 1.5}}", "1.5"),
+        (r"{{number value precision=2}}", "1234567.89"),
+        (
+            r#"{{number value precision=1 separator=","}}"#,
+            "1,234,567.9",
+        ),
+        (r#"{{number count separator=","}}"#, "-1,234,567"),
+        (r#"{{number 999 separator=","}}"#, "999"),
+        (r"{{number 2 precision=3}}", "2.000"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn number_percent() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"ratio": 0.25, "large": 12.5});
+    let cases = [
+        (r"{{percent ratio}}", "25%"),
+        (r"{{percent 0.5 precision=1}}", "50.0%"),
+        (r#"{{percent large separator=","}}"#, "1,250%"),
+        (r"{{percent 0.07}}", "7%"),
+        (r"{{percent 0.29}}", "29%"),
+        (r"{{percent 0.123456}}", "12.3456%"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn number_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    assert!(registry.once(NAME, r#"{{number "foo"}}"#, &data).is_err());
+    assert!(registry.once(NAME, r#"{{percent "foo"}}"#, &data).is_err());
+    assert!(registry
+        .once(NAME, r"{{number 1 precision=-1}}", &data)
+        .is_err());
+    assert!(registry
+        .once(NAME, r"{{number 1 separator=1}}", &data)
+        .is_err());
+    let limits = [
+        r"{{number 1 precision=1000000000}}",
+        r"{{percent 1 precision=101}}",
+        r"{{filesize 1000 precision=101}}",
+    ];
+    for value in limits.iter() {
+        match registry.once(NAME, value, &data) {
+            Err(Error::Render(e)) => assert_eq!(
+                HelperError::ParameterLimit(
+                    value[2..].split(' ').next().unwrap().to_string(),
+                    "precision".to_string(),
+                    MAX_PRECISION,
+                )
+                .to_string(),
+                e.to_string(),
+                "{}",
+                value
+            ),
+            _ => panic!("expected precision limit error for {}", value),
+        }
+    }
+    assert!(registry
+        .once(NAME, r"{{number 1 precision=100}}", &data)
+        .is_ok());
+    Ok(())
+}
+
+#[test]
+fn number_percent_non_finite() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({"big": 1e307, "small": -1e307});
+    let value = r"[{{percent big}}][{{percent small precision=2}}]";
+    assert_eq!("[][]", registry.once(NAME, value, &data)?);
+    registry.set_non_finite_repr(NonFinite::Name);
+    assert_eq!("[Infinity][-Infinity]", registry.once(NAME, value, &data)?);
+    registry.set_non_finite_repr(NonFinite::Token("n/a".to_string()));
+    assert_eq!("[n/a][n/a]", registry.once(NAME, value, &data)?);
+    assert_eq!("50%", registry.once(NAME, r"{{percent 0.5}}", &data)?);
+    Ok(())
+}
+
+#[test]
+fn number_filesize() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"bytes": 1_200_000});
+    let cases = [
+        (r"{{filesize 0}}", "0 B"),
+        (r"{{filesize 999}}", "999 B"),
+        (r"{{filesize 1000}}", "1.0 KB"),
+        (r"{{filesize bytes}}", "1.2 MB"),
+        (r"{{filesize bytes precision=2}}", "1.20 MB"),
+        (r"{{filesize bytes binary=true}}", "1.1 MiB"),
+        (r"{{filesize 1023 binary=true}}", "1023 B"),
+        (r"{{filesize 1536 binary=true precision=0}}", "2 KiB"),
+        (r"{{filesize 999999}}", "1.0 MB"),
+        (r"{{filesize 999949}}", "999.9 KB"),
+        (r"{{filesize 999499 precision=0}}", "999 KB"),
+        (r"{{filesize 1048575 binary=true}}", "1.0 MiB"),
+        (r"{{filesize 1500000.0}}", "1.5 MB"),
+        (r"{{filesize 10.4}}", "10 B"),
+        (r"{{filesize 999.7}}", "1.0 KB"),
+        (r"{{filesize 0.0}}", "0 B"),
+        (r"{{filesize 5000000000000}}", "5.0 TB"),
+        (r"{{filesize 18446744073709551615}}", "18.4 EB"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry.once(NAME, r"{{filesize -1}}", &data).is_err());
+    assert!(registry.once(NAME, r"{{filesize -0.5}}", &data).is_err());
+    assert!(registry.once(NAME, r"{{filesize null}}", &data).is_err());
+    assert!(registry.once(NAME, r#"{{filesize "1"}}"#, &data).is_err());
+    Ok(())
+}
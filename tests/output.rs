@@ -0,0 +1,163 @@
+use bracket::{error::RenderError, output::Writer, Error, Registry, Result};
+use serde_json::json;
+use std::fmt;
+use std::io::Write;
+
+const NAME: &str = "output.rs";
+
+// Records the buffer content each time the writer is flushed.
+#[derive(Default)]
+struct Chunks {
+    buffer: Vec<u8>,
+    chunks: Vec<String>,
+}
+
+impl Write for Chunks {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let chunk = String::from_utf8(self.buffer.split_off(0)).unwrap();
+        self.chunks.push(chunk);
+        Ok(())
+    }
+}
+
+#[test]
+fn output_flush_render() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, "{{foo}} {{bar}}")?;
+    let data = json!({"foo": "a", "bar": "b"});
+    let mut writer = Writer::new(Chunks::default());
+    registry.render_to_write(NAME, &data, &mut writer)?;
+    assert_eq!(vec!["a b".to_string()], writer.into_inner().chunks);
+    Ok(())
+}
+
+#[test]
+fn output_flush_statements() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_flush_statements(true);
+    registry.insert(NAME, "{{foo}} {{#if bar}}{{bar}}{{/if}}.")?;
+    let data = json!({"foo": "a", "bar": "b"});
+    let mut writer = Writer::new(Chunks::default());
+    registry.render_to_write(NAME, &data, &mut writer)?;
+    let expected = vec!["a", " b", "."];
+    assert_eq!(expected, writer.into_inner().chunks);
+    Ok(())
+}
+
+#[test]
+fn output_render_chunks() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, "<p>{{foo}}</p>{{#each list}}{{this}}{{/each}}!")?;
+    let data = json!({"foo": "<a>", "list": [1, 2]});
+    let mut chunks: Vec<String> = Vec::new();
+    registry.render_chunks(NAME, &data, |chunk| {
+        chunks.push(chunk);
+        Ok(())
+    })?;
+    assert_eq!(vec!["<p>&lt;a&gt;", "</p>12", "!"], chunks);
+    Ok(())
+}
+
+#[test]
+fn output_render_chunks_error() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, "{{foo}}{{bar}}")?;
+    let data = json!({"foo": "foo", "bar": "bar"});
+    let mut chunks: Vec<String> = Vec::new();
+    let result = registry.render_chunks(NAME, &data, |chunk| {
+        chunks.push(chunk);
+        Err(std::io::Error::new(
+            std::io::ErrorKind::BrokenPipe,
+            "disconnected",
+        ))
+    });
+    match result {
+        Err(Error::Render(RenderError::OutputWrite(_, written))) => {
+            assert_eq!(3, written);
+        }
+        _ => panic!("Expecting output write error"),
+    }
+    assert_eq!(vec!["foo"], chunks);
+    Ok(())
+}
+
+// Fails all writes once the limit has been reached.
+struct Disconnect {
+    limit: usize,
+    buffer: Vec<u8>,
+}
+
+impl Write for Disconnect {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.buffer.len() + buf.len() > self.limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "disconnected",
+            ));
+        }
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn output_write_error() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, "{{foo}} {{bar}}")?;
+    let data = json!({"foo": "foo", "bar": "bar"});
+    let mut writer = Writer::new(Disconnect {
+        limit: 5,
+        buffer: Vec::new(),
+    });
+    let result = registry.render_to_write(NAME, &data, &mut writer);
+    match result {
+        Err(Error::Render(RenderError::OutputWrite(ref e, written))) => {
+            assert_eq!(4, written);
+            assert!(e.to_string().contains("disconnected"));
+        }
+        _ => panic!("Expecting output write error"),
+    }
+    assert_eq!(
+        "Failed to write output after 4 byte(s), disconnected",
+        result.err().unwrap().to_string()
+    );
+    assert_eq!(b"foo ", &writer.into_inner().buffer[..]);
+    Ok(())
+}
+
+struct Page<'a> {
+    registry: &'a Registry<'a>,
+    title: &'a str,
+}
+
+impl fmt::Display for Page<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.registry
+            .render_to_fmt("page", &json!({"title": self.title}), f)
+            .map_err(|_| fmt::Error)
+    }
+}
+
+#[test]
+fn output_render_to_fmt() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "<h1>{{title}}</h1>")?;
+    let mut buffer = String::from("<body>");
+    registry.render_to_fmt("page", &json!({"title": "foo"}), &mut buffer)?;
+    assert_eq!("<body><h1>foo</h1>", buffer);
+    let page = Page {
+        registry: &registry,
+        title: "bar",
+    };
+    assert_eq!("<h1>bar</h1>", page.to_string());
+    Ok(())
+}
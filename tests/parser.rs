@@ -691,3 +691,95 @@ fn parse_raw_block() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn parse_absolute_path() -> Result<()> {
+    let value = "{{foo /a/b qux=/c}}";
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+
+    match node {
+        Node::Document(doc) => {
+            assert_eq!(1, doc.nodes().len());
+            match doc.nodes().first().unwrap() {
+                Node::Statement(call) => {
+                    assert_eq!(1, call.arguments().len());
+                    match call.arguments().first().unwrap() {
+                        ParameterValue::Path(path) => {
+                            assert!(path.absolute());
+                            assert_eq!(2, path.components().len());
+                        }
+                        _ => panic!("Expecting path argument!"),
+                    }
+                    match call.parameters().get("qux").unwrap() {
+                        ParameterValue::Path(path) => {
+                            assert!(path.absolute());
+                            assert_eq!(1, path.components().len());
+                        }
+                        _ => panic!("Expecting path parameter!"),
+                    }
+                }
+                _ => panic!("Expecting statement node!"),
+            }
+        }
+        _ => panic!("Bad root node type for parser()."),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_statement_path_dotted() -> Result<()> {
+    let cases = [
+        ("{{foo.bar.baz}}", "foo.bar.baz"),
+        ("{{foo/bar/baz}}", "foo.bar.baz"),
+        ("{{../../foo/bar}}", "../../foo.bar"),
+        ("{{./foo}}", "this.foo"),
+        ("{{this/foo}}", "this.foo"),
+        ("{{@root.foo}}", "@root.foo"),
+        ("{{foo.[bar baz]}}", "foo.bar baz"),
+    ];
+    for (value, expected) in cases.iter() {
+        let mut parser = Parser::new(value, Default::default());
+        let node = parser.parse()?;
+        match node {
+            Node::Document(doc) => match doc.nodes().first().unwrap() {
+                Node::Statement(ref call) => match call.target() {
+                    CallTarget::Path(ref path) => {
+                        assert_eq!(expected, &path.to_dotted());
+                    }
+                    _ => panic!("Expecting path call target"),
+                },
+                _ => panic!("Expecting statement node."),
+            },
+            _ => panic!("Bad root node type for parser()."),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_partial_error() -> Result<()> {
+    let value = "foo {{bar}} {{#if baz}}qux{{/if}} {{/foo}} {{bar}}";
+    let mut parser = Parser::new(value, Default::default());
+    let (nodes, error) = parser.parse_partial();
+    assert_eq!(5, nodes.len());
+    assert_eq!("{{#if baz}}qux{{/if}}", nodes[3].as_str());
+    let (error, byte) = error.unwrap();
+    assert!(matches!(
+        error,
+        bracket::error::SyntaxError::BlockNotOpen(_)
+    ));
+    assert_eq!(value.find("{{/foo}}").unwrap(), byte);
+    Ok(())
+}
+
+#[test]
+fn parse_partial_complete() -> Result<()> {
+    let value = "foo {{bar}}";
+    let mut parser = Parser::new(value, Default::default());
+    let (nodes, error) = parser.parse_partial();
+    assert_eq!(2, nodes.len());
+    assert!(error.is_none());
+    Ok(())
+}
@@ -1,5 +1,6 @@
-use bracket::{Registry, Result};
+use bracket::{error::RenderError, Error, Registry, Result};
 use serde_json::json;
+use std::collections::HashMap;
 
 const NAME: &str = "partial.rs";
 
@@ -39,6 +40,34 @@ fn partial_block() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn partial_block_default() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(
+        "layout",
+        "<{{#> @partial-block}}default{{/@partial-block}}>",
+    )?;
+    registry.insert("wrapper", "[{{> @partial-block}}]")?;
+
+    let data = json!({"bar": "qux"});
+    let cases = vec![
+        ("{{#> layout}}{{bar}}{{/layout}}", "<qux>"),
+        ("{{> layout}}", "<default>"),
+        ("{{#> layout}}{{/layout}}", "<>"),
+        ("{{#> layout}}a{{/layout}}{{> layout}}", "<a><default>"),
+        (
+            "{{#> wrapper}}{{#> layout}}b{{/layout}}{{/wrapper}}",
+            "[<b>]",
+        ),
+        ("{{#> wrapper}}{{> layout}}{{/wrapper}}", "[<default>]"),
+    ];
+    for (value, expected) in cases {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
 #[test]
 fn partial_context() -> Result<()> {
     let mut registry = Registry::new();
@@ -62,3 +91,250 @@ fn partial_context_parameter() -> Result<()> {
     assert_eq!("xyz", &result);
     Ok(())
 }
+
+#[test]
+fn partial_sub_expr_each() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("a", "A{{this.name}}")?;
+    registry.insert("b", "B{{this.name}}")?;
+
+    let value = r"{{#each items}}{{ > (lookup this 'kind') this }}{{/each}}";
+    let data = json!({"items": [
+        {"kind": "a", "name": "1"},
+        {"kind": "b", "name": "2"},
+        {"kind": "a", "name": "3"},
+    ]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("A1B2A3", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_sub_expr_not_found() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{ > (foo) }}";
+    let data = json!({"foo": "bar"});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Expecting partial not found error"),
+        Err(e) => {
+            assert_eq!(
+                Error::Render(RenderError::DynamicPartialNotFound(
+                    "bar".to_string(),
+                    "(foo)".to_string()
+                )),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn partial_inline() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#*inline "foo"}}{{bar}}{{/inline}}<{{> foo}}>"#;
+    let data = json!({"bar": "qux"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<qux>", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_inline_layout() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("layout", "<main>{{> content}}</main>")?;
+
+    let value =
+        r#"{{#> layout}}{{#*inline "content"}}{{bar}}{{/inline}}{{/layout}}"#;
+    let data = json!({"bar": "qux"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<main>qux</main>", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_inline_scope() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("foo", "global")?;
+    registry
+        .insert("inner", r#"{{#*inline "foo"}}inner{{/inline}}{{> foo}}"#)?;
+    registry.insert("sibling", "{{> foo}}")?;
+    let data = json!({});
+    let cases = [
+        (
+            r"{{> inner}}|{{> sibling}}|{{> foo}}",
+            "inner|global|global",
+        ),
+        (
+            r#"{{#if true}}{{#*inline "foo"}}block{{/inline}}{{> foo}}{{/if}}|{{> foo}}"#,
+            "block|global",
+        ),
+        (
+            r#"{{#*inline "foo"}}outer{{/inline}}{{> inner}}|{{> sibling}}|{{> foo}}"#,
+            "inner|outer|outer",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn partial_inline_standalone() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{#*inline \"foo\"}}\n{{bar}}\n{{/inline}}\n{{> foo}}";
+    let data = json!({"bar": "qux"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("qux\n", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_inline_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    let result = registry.once(NAME, r"{{#*inline}}{{/inline}}", &data);
+    assert_eq!(
+        Error::Render(RenderError::InlinePartial("{{#*inline}}".to_string())),
+        result.err().unwrap()
+    );
+    let result = registry.once(NAME, r#"{{#*foo "bar"}}{{/foo}}"#, &data);
+    assert_eq!(
+        Error::Render(RenderError::DecoratorNotFound("foo".to_string())),
+        result.err().unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn partial_inherit_context() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("address", "{{name}}|{{this.name}}")?;
+    let value = r"{{#with company}}{{> address}}{{/with}}";
+    let data = json!({"company": {"name": "acme"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("acme|acme", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_inherit_context_hash() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("address", "{{this.name}} {{this.country}}")?;
+    let value = r#"{{#with company}}{{> address country="UK"}}{{/with}}"#;
+    let data = json!({"company": {"name": "acme"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("acme UK", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_inherit_context_each() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("item", "<{{this}}>")?;
+    let value = r"{{#each list}}{{> item}}{{/each}}";
+    let data = json!({"list": ["foo", "bar"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<foo><bar>", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_render_with_partials() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{> header}}|{{> footer}}")?;
+    registry.insert("footer", "registered")?;
+    let header = registry.parse("header", "<h1>{{title}}</h1>")?;
+    let footer = registry.parse("footer", "override")?;
+    let mut partials = HashMap::new();
+    partials.insert("header".to_string(), header.node());
+    partials.insert("footer".to_string(), footer.node());
+    let data = json!({"title": "foo"});
+    let result = registry.render_with_partials("page", &data, &partials)?;
+    assert_eq!("<h1>foo</h1>|override", &result);
+    // Partials are not added to the registry
+    let result = registry.render("page", &data);
+    assert_eq!(
+        Error::from(RenderError::PartialNotFound("header".to_string())),
+        result.unwrap_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn partial_read_dir_prefixed() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.read_dir_prefixed("tests/fixtures/partials/ui", "hbs", "ui/")?;
+    registry.read_dir_prefixed(
+        "tests/fixtures/partials/form",
+        "hbs",
+        "form/",
+    )?;
+    assert!(registry.get("ui/button").is_some());
+    assert!(registry.get("form/button").is_some());
+    assert!(registry.get("button").is_none());
+    let value = r"{{> ui/button}}{{> form/button}}";
+    let data = json!({"label": "Go"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<button>Go</button><input type="button" value="Go">"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn partial_nested_name_invalid() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("ui/button", "foo")?;
+    let data = json!({});
+    for value in [r"{{> this/ui/button}}", r"{{> ../ui/button}}"].iter() {
+        match registry.once(NAME, value, &data) {
+            Ok(_) => panic!("Expecting partial identifier error"),
+            Err(e) => assert!(e
+                .to_string()
+                .starts_with("Partial names must be identifiers")),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn partial_relative() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("header", "global")?;
+    registry.insert("footer", "footer")?;
+    registry.insert("pages/header", "page {{> nav}}")?;
+    registry.insert("pages/nav", "nav")?;
+    registry.insert("nav", "global nav")?;
+    registry.insert("pages/home", "{{> header}}|{{> footer}}")?;
+    registry.insert(
+        "pages/inline",
+        "{{#*inline \"header\"}}inline{{/inline}}{{> header}}",
+    )?;
+    registry.insert("home", "{{> header}}|{{> pages/header}}")?;
+    registry.insert("main", "global main")?;
+    registry.insert("pages/main", "[{{> main}}]")?;
+    registry.insert("pages/layout", "{{> main}}!")?;
+    let data = json!({});
+
+    assert_eq!("global|footer", registry.render("pages/home", &data)?);
+
+    registry.set_relative_partials(true);
+    assert_eq!("page nav|footer", registry.render("pages/home", &data)?);
+    assert_eq!("inline", registry.render("pages/inline", &data)?);
+    assert_eq!("global|page nav", registry.render("home", &data)?);
+    assert_eq!("[global main]", registry.render("pages/main", &data)?);
+    assert_eq!("[global main]!", registry.render("pages/layout", &data)?);
+
+    assert!(registry.lint_strict("pages/about", "{{> nav}}")?.is_empty());
+    assert_eq!(
+        vec![Error::from(RenderError::PartialNotFound(
+            "menu".to_string()
+        ))],
+        registry.lint_strict("pages/about", "{{> menu}}")?
+    );
+    Ok(())
+}
@@ -50,6 +50,17 @@ fn raw_block() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn raw_block_name() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{{{code}}}}foo {{bar}} baz{{{{/code}}}}";
+    let expected = r"foo {{bar}} baz";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(expected, result);
+    Ok(())
+}
+
 #[test]
 fn raw_block_multiline() -> Result<()> {
     let registry = Registry::new();
@@ -68,3 +79,60 @@ text";
     assert_eq!(expected, result);
     Ok(())
 }
+
+#[test]
+fn raw_block_not_escaped() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{{{raw}}}}<foo> {{bar}}{{{{/raw}}}}";
+    let expected = r"<foo> {{bar}}";
+    let data = json!({"bar": "<qux>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(expected, result);
+    Ok(())
+}
+
+#[test]
+fn raw_block_call_not_escaped() -> Result<()> {
+    let registry = Registry::new();
+    let template = registry.parse(NAME, r"{{{{raw}}}}<foo>{{{{/raw}}}}")?;
+    let node = template.node().into_iter().next().unwrap();
+    if let Node::Block(block) = node {
+        assert!(!block.call().is_escaped());
+    } else {
+        panic!("Expecting raw block node");
+    }
+    Ok(())
+}
+
+#[test]
+fn raw_block_verbatim() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"x": true});
+    let cases = [
+        (
+            r"{{{{raw}}}}{{ this is literal }}{{{{/raw}}}}",
+            r"{{ this is literal }}",
+        ),
+        (
+            r"{{{{raw}}}}{{#if x}}y{{else}}n{{/if}}{{{{/raw}}}}",
+            r"{{#if x}}y{{else}}n{{/if}}",
+        ),
+        (
+            r"{{{{raw}}}}{{!-- c --}}\{{x}}{{{{/raw}}}}",
+            r"{{!-- c --}}\{{x}}",
+        ),
+        (r"{{{{raw}}}}a {{{{ b{{{{/raw}}}}", r"a {{{{ b"),
+        (r"{{{{raw}}}}a {{{{}}}} b{{{{/raw}}}}", r"a {{{{}}}} b"),
+        (r"{{{{raw}}}}}}}} {{{ }}}{{{{/raw}}}}", r"}}}} {{{ }}}"),
+        (
+            r"{{{{raw}}}}a {{{{raw}}}}x{{{{/raw}}}} b{{{{/raw}}}}",
+            r"a {{{{raw}}}}x{{{{/raw}}}} b",
+        ),
+        (r"{{{{raw}}}}{{{{/raw}}}}{{x}}", r"true"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
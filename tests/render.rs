@@ -1,4 +1,4 @@
-use bracket::{Registry, Result};
+use bracket::{error::RenderError, Error, Registry, Result};
 use serde_json::json;
 
 const NAME: &str = "render.rs";
@@ -45,6 +45,18 @@ fn render_raw_comment() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn render_raw_comment_nested() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        r"a{{!-- {{#if foo}}{{bar}}{{/if}} -- }} {{! baz }} --}}b{{!----}}c";
+    let expected = r"abc";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(expected, result);
+    Ok(())
+}
+
 #[test]
 fn render_raw_statement() -> Result<()> {
     let registry = Registry::new();
@@ -56,6 +68,17 @@ fn render_raw_statement() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn render_raw_statement_keep_escape() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strip_raw_escape(false);
+    let value = r"\{{foo}} \{{{bar}}} {{title}}";
+    let data = json!({"title": "qux"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r"\{{foo}} \{{{bar}}} qux", result);
+    Ok(())
+}
+
 #[test]
 fn render_statement() -> Result<()> {
     let registry = Registry::new();
@@ -66,3 +89,209 @@ fn render_statement() -> Result<()> {
     assert_eq!(expected, result);
     Ok(())
 }
+
+#[test]
+fn render_budget() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_render_budget(Some(5));
+    let value = r"{{#each foo}}{{this}}{{/each}}";
+    let data = json!({"foo": [1, 2]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("12", &result);
+
+    let data = json!({"foo": [1, 2, 3, 4, 5, 6]});
+    let result = registry.once(NAME, value, &data);
+    assert_eq!(
+        "Render budget of 5 operations exceeded",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn render_max_iterations() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_max_iterations(Some(3));
+    let data = json!({"list": [1, 2, 3, 4], "obj": {"a": 1, "b": 2}});
+    let cases = [
+        (r"{{#each obj}}{{@key}}{{/each}}", "ab"),
+        (r"{{#each list limit=3}}{{this}}{{/each}}", "123"),
+        (r"{{#each (range 0 3)}}{{this}}{{/each}}", "012"),
+        (r#"{{repeat "a" 3}}"#, "aaa"),
+        (r"{{#repeat 3}}{{@index}}{{/repeat}}", "012"),
+        (r"{{#chunk list 2}}{{len this}}{{/chunk}}", "22"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+
+    let cases = [
+        (r"{{#each list}}{{this}}{{/each}}", "each", 4),
+        (r"{{#each (range 0 100)}}{{/each}}", "range", 100),
+        (r#"{{repeat "a" 4}}"#, "repeat", 4),
+        (r"{{#repeat 5}}{{/repeat}}", "repeat", 5),
+        (r"{{#chunk list 1}}{{/chunk}}", "chunk", 4),
+    ];
+    for (value, name, count) in cases.iter() {
+        let result = registry.once(NAME, value, &data);
+        assert_eq!(
+            format!(
+                "Helper '{}' expansion of {} iterations exceeds the limit of 3",
+                name, count
+            ),
+            result.err().unwrap().to_string()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn render_budget_exceeded() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_render_budget(Some(2));
+    let value = r"{{foo}}{{foo}}{{foo}}";
+    let data = json!({"foo": "bar"});
+    let result = registry.once(NAME, value, &data);
+    assert_eq!(
+        Error::Render(RenderError::BudgetExceeded(2)),
+        result.err().unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn render_bom() -> Result<()> {
+    let registry = Registry::new();
+    let value = "\u{FEFF}{{foo}}";
+    let data = json!({"foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("bar", &result);
+
+    let mut registry = Registry::new();
+    registry.set_delimiters("<%", "%>")?;
+    let result = registry.once(NAME, "\u{FEFF}a <%foo%>", &data)?;
+    assert_eq!("a bar", &result);
+    Ok(())
+}
+
+#[test]
+fn render_bom_preserve() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_preserve_bom(true);
+    let value = "\u{FEFF}{{foo}}";
+    let data = json!({"foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("\u{FEFF}bar", &result);
+    Ok(())
+}
+
+#[test]
+fn render_line_comment() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"list": ["a", "b"], "name": "foo"});
+    let cases = [
+        ("{{name ; the name}}", "foo"),
+        ("{{; leading\nname}}", "foo"),
+        (
+            "{{json list ; the list\n  pretty=true ; pretty\n  indent=0\n}}",
+            "[\n&quot;a&quot;,\n&quot;b&quot;\n]",
+        ),
+        ("{{eq (len list ; count\n) 2 ; two\n}}", "true"),
+        ("{{eq (len list ; count) 2}}", "true"),
+        (
+            "{{#if name ; check\n}}yes{{else ; otherwise\n}}no{{/if}}",
+            "yes",
+        ),
+        ("{{name ; trim ~}}  bar", "foobar"),
+        (r#"{{json "a;b"}}"#, "&quot;a;b&quot;"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn render_validate() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    registry.insert("a", "{{title}}")?;
+    registry.insert("b", "{{> missing}}")?;
+    registry.insert("c", "{{> a}} {{author}}")?;
+    registry.insert("d", "{{#if title}}{{> a}}{{/if}}")?;
+    let data = json!({"title": "foo"});
+    let errors = registry.validate(&data);
+    assert_eq!(
+        vec![
+            (
+                "b".to_string(),
+                RenderError::PartialNotFound("missing".to_string())
+            ),
+            (
+                "c".to_string(),
+                RenderError::VariableNotFound(
+                    "author".to_string(),
+                    "c".to_string()
+                )
+            ),
+        ],
+        errors
+    );
+    let errors = registry.validate(&json!({"author": "bar"}));
+    let names: Vec<&str> = errors.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(vec!["a", "b", "c"], names);
+    Ok(())
+}
+
+#[test]
+fn render_value_ref() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("item", "{{name}}@{{@root.title}}")?;
+    registry.insert("list", "{{#each items}}{{> item}};{{/each}}")?;
+    let data = json!({"title": "foo", "items": [{"name": "a"}, {"name": "b"}]});
+    let result = registry.render_value_ref("list", &data)?;
+    assert_eq!("a@foo;b@foo;", &result);
+    assert_eq!(registry.render("list", &data)?, result);
+    Ok(())
+}
+
+#[test]
+fn render_value_ref_missing() -> Result<()> {
+    let registry = Registry::new();
+    let result = registry.render_value_ref("missing", &json!({}));
+    assert!(matches!(result, Err(Error::TemplateNotFound(_))));
+    Ok(())
+}
+
+#[test]
+fn render_with_sourcemap() -> Result<()> {
+    let mut registry = Registry::new();
+    let source = "Hi {{name}}!{{#each list}}<{{this}}>{{/each}}{{> item}}";
+    registry.insert("item", "[{{name}}]")?;
+    registry.insert("page", source)?;
+    let data = json!({"name": "a&b", "list": [1]});
+    let (result, source_map) = registry.render_with_sourcemap("page", &data)?;
+    assert_eq!("Hi a&amp;b!<1>[a&amp;b]", &result);
+    assert_eq!(registry.render("page", &data)?, result);
+
+    let mapped: Vec<(&str, &str)> = source_map
+        .iter()
+        .map(|(out, src)| (&result[out.clone()], &source[src.clone()]))
+        .collect();
+    let expected = vec![
+        ("Hi ", "Hi "),
+        ("a&amp;b", "{{name}}"),
+        ("!", "!"),
+        ("<", "<"),
+        ("1", "{{this}}"),
+        (">", ">"),
+        ("[a&amp;b]", "{{> item}}"),
+    ];
+    assert_eq!(expected, mapped);
+
+    let result = registry.render_with_sourcemap("missing", &data);
+    assert!(matches!(result, Err(Error::TemplateNotFound(_))));
+    Ok(())
+}
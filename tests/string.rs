@@ -0,0 +1,216 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "string.rs";
+
+#[test]
+fn repeat_statement() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"[{{repeat "ab" 3}}]"#;
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[ababab]", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_block() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat count}}{{@index}}:{{foo}} {{/repeat}}";
+    let data = json!({"count": 3, "foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("0:bar 1:bar 2:bar ", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_block_each() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each items}}{{#repeat 2}}{{this}}{{/repeat}}{{/each}}";
+    let data = json!({"items": ["a", "b"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("aabb", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_invalid_count() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    assert!(registry.once(NAME, r#"{{repeat "a" -1}}"#, &data).is_err());
+    assert!(registry.once(NAME, r#"{{repeat "a" 1.5}}"#, &data).is_err());
+    assert!(registry
+        .once(NAME, r#"{{repeat "a" 10001}}"#, &data)
+        .is_err());
+    assert!(registry.once(NAME, r"{{repeat 1 2}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn repeat_length_limit() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"value": "a".repeat(1000)});
+    let result = registry.once(NAME, r"{{repeat value 1000}}", &data)?;
+    assert_eq!(1_000_000, result.len());
+    let result = registry.once(NAME, r"{{repeat value 1001}}", &data);
+    assert_eq!(
+        "Helper 'repeat' got invalid arguments, maximum length is 1000000",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn len_values() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "list": [1, 2, 3],
+        "map": {"a": 1, "b": 2},
+        "family": "👨‍👩‍👧",
+        "accent": "e\u{301}té",
+    });
+    let cases = [
+        (r"{{len list}}", "3"),
+        (r"{{len map}}", "2"),
+        (r"{{len family}}", "1"),
+        (r"{{len accent}}", "3"),
+        (r#"{{len ""}}"#, "0"),
+        (r"{{#if (gt (len list) 2)}}many{{/if}}", "many"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn len_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    assert!(registry.once(NAME, r"{{len 1}}", &data).is_err());
+    assert!(registry.once(NAME, r"{{len true}}", &data).is_err());
+    assert!(registry.once(NAME, r"{{len null}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn indent_block() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"name": "foo", "items": ["a", "b"]});
+    let cases = [
+        (r#"{{#indent "  "}}{{name}}{{/indent}}"#, "  foo"),
+        (
+            "root:\n{{#indent \"  \"}}\nname: {{name}}\nlist:\n{{/indent}}",
+            "root:\n  name: foo\n  list:\n",
+        ),
+        ("{{#indent \"> \"}}a\n\nb\n{{/indent}}", "> a\n\n> b\n"),
+        (
+            "{{#indent \"> \" blanks=true}}a\n\nb\n{{/indent}}",
+            "> a\n> \n> b\n",
+        ),
+        ("{{#indent \"- \"}}a\r\n\r\nb{{/indent}}", "- a\r\n\r\n- b"),
+        (
+            "{{#indent \"  \"}}{{#each items}}{{this}}\n{{/each}}{{/indent}}",
+            "  a\n  b\n",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry.once(NAME, r#"{{indent "  "}}"#, &data).is_err());
+    assert!(registry
+        .once(NAME, r"{{#indent 2}}foo{{/indent}}", &data)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn titlecase_words() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"title": "the lord OF the rings", "small": ["a", "the", "of"],
+        "spaced": "  hello \t world\n",
+        "list": [1]});
+    let cases = [
+        (r#"{{titlecase "hello world"}}"#, "Hello World"),
+        (r#"{{titlecase "HELLO wORLD"}}"#, "Hello World"),
+        (r"{{titlecase spaced}}", "  Hello \t World\n"),
+        (r#"{{titlecase "élan über ßtraße"}}"#, "Élan Über Sstraße"),
+        (r#"{{titlecase "ﬁsh"}}"#, "Fish"),
+        (r#"{{titlecase ""}}"#, ""),
+        (r"{{titlecase title}}", "The Lord Of The Rings"),
+        (
+            r"{{titlecase title small_words=small}}",
+            "The Lord of the Rings",
+        ),
+        (
+            r#"{{titlecase "a tale of two cities" small_words="A of"}}"#,
+            "A Tale of Two Cities",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry.once(NAME, r"{{titlecase 1}}", &data).is_err());
+    assert!(registry
+        .once(NAME, r#"{{titlecase "a" small_words=1}}"#, &data)
+        .is_err());
+    assert!(registry
+        .once(NAME, r"{{titlecase title small_words=list}}", &data)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn classnames() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"active": true, "count": 0, "name": "card"});
+    let cases = [
+        (
+            r#"{{classnames "btn" primary=true muted=false}}"#,
+            "btn primary",
+        ),
+        (
+            r#"{{classnames "btn" active=active empty=count}}"#,
+            "btn active",
+        ),
+        (r#"{{classnames name "" "wide"}}"#, "card wide"),
+        (r"{{classnames a=1 b=null c=true}}", "a c"),
+        (r"{{classnames}}", ""),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry.once(NAME, r"{{classnames 1}}", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn trim() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("item", "\n  {{name}}  \n")?;
+    let data = json!({"name": "foo", "padded": "  bar  "});
+    let cases = [
+        (r"[{{#trim}}  {{padded}}  {{/trim}}]", "[bar]"),
+        (
+            r"[{{#trim_start}}  {{padded}}  {{/trim_start}}]",
+            "[bar    ]",
+        ),
+        (r"[{{#trim_end}}  {{padded}}  {{/trim_end}}]", "[    bar]"),
+        (r"[{{#trim}}{{> item}}{{/trim}}]", "[foo]"),
+        (r"[{{#trim}}{{#if padded}} yes {{/if}}{{/trim}}]", "[yes]"),
+        (r"[{{#trim}}   {{/trim}}]", "[]"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    assert!(registry.once(NAME, r"{{trim padded}}", &data).is_err());
+    assert!(registry
+        .once(NAME, r"{{#trim 1}}x{{/trim}}", &data)
+        .is_err());
+    Ok(())
+}
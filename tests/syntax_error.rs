@@ -2,6 +2,7 @@ use bracket::{
     error::{Error, ErrorInfo, SourcePos, SyntaxError},
     Registry, Result,
 };
+use serde_json::json;
 
 const NAME: &str = "syntax_error.rs";
 
@@ -217,3 +218,200 @@ fn syntax_err_raw_block_half_open() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn syntax_err_fragment_line_offset() -> Result<()> {
+    let registry = Registry::new();
+    let value = "foo\n{{}}";
+    match registry.compile_fragment(value, NAME, 12, 0) {
+        Ok(_) => panic!("Identifier error expected (empty statement)"),
+        Err(e) => {
+            let pos = SourcePos(13, 6);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::ExpectedIdentifier(info.into())),
+                e
+            );
+            assert!(format!("{:?}", e).contains("syntax_error.rs:14:"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_raw_block_name_mismatch() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{{{code}}}}foo{{{{/raw}}}}"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Raw block tag name mismatch error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(0, 23);
+            let notes = vec!["opening name is 'code'".to_string()];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::TagNameMismatch(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_comment_statement() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{! note:\nuse {{x}} here }}";
+    assert!(registry.parse(NAME, value).is_ok());
+    let errors = registry.lint(NAME, value)?;
+    let pos = SourcePos(1, 14);
+    let notes =
+        vec!["use a raw comment {{!-- --}} to comment out statements"
+            .to_string()];
+    let info = ErrorInfo::new(value, NAME, pos, notes);
+    assert_eq!(
+        vec![Error::Syntax(SyntaxError::CommentStatement(info.into()))],
+        errors
+    );
+    Ok(())
+}
+
+#[test]
+fn syntax_err_comment_statement_stray() -> Result<()> {
+    let registry = Registry::new();
+    let value = "a{{! see {{foo}} }}";
+    assert_eq!("a }}", &registry.once(NAME, value, &json!({}))?);
+    let errors = registry.lint(NAME, value)?;
+    let pos = SourcePos(0, 9);
+    let notes =
+        vec!["use a raw comment {{!-- --}} to comment out statements"
+            .to_string()];
+    let info = ErrorInfo::new(value, NAME, pos, notes);
+    assert_eq!(
+        vec![Error::Syntax(SyntaxError::CommentStatement(info.into()))],
+        errors
+    );
+
+    let value = "{{! about {{ }}\nJSON: {\"a\": {\"b\": 1}}";
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!("JSON: {\"a\": {\"b\": 1}}", &result);
+
+    // Open tags without a stray close tag are comment text
+    let cases = [
+        "{{! use {{ to open a statement }}",
+        "{{! see {{foo }}{{bar}}",
+        "{{! see {{foo }}\n{{#if bar}}{{/if}}",
+    ];
+    for value in cases.iter() {
+        assert!(registry.lint(NAME, value)?.is_empty(), "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_duplicate_hash_key() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{foo a=1 b=(bar a=1) a=2}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Duplicate hash key error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(0, 22);
+            let notes = vec!["key 'a' is already defined".to_string()];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::DuplicateHashKey(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_crlf_fourth_line() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{foo\r\n  bar}}\r\n{{! comment\r\n}} {{/if}}\r\n";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Block not open error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            // Zero-based line index so this is the fourth line
+            let pos = SourcePos(3, 32);
+            let notes = vec!["perhaps open the block 'if'".to_string()];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::BlockNotOpen(info.into())),
+                e
+            );
+            let message = format!("{:?}", e);
+            assert!(message.contains(" 4 | }} {{/if}}\n"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_bom() -> Result<()> {
+    let registry = Registry::new();
+    let value = "\u{FEFF}{{foo}}\n{{/if}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Block not open error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(1, 11);
+            let notes = vec!["perhaps open the block 'if'".to_string()];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::BlockNotOpen(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_source_context() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{foo}}\n  {{# }}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Identifier error expected"),
+        Err(e) => {
+            let context = e.source_context().unwrap();
+            assert_eq!(NAME, context.file_name());
+            assert_eq!(2, context.line());
+            assert_eq!(7, context.column());
+            assert_eq!(14, context.byte_offset());
+            assert_eq!("  {{# }}", context.source_line());
+            assert!(context.notes().is_empty());
+            assert!(format!("{:?}", e).ends_with(&context.to_string()));
+            match e {
+                Error::Syntax(ref e) => {
+                    assert_eq!(Some(2), e.line());
+                    assert_eq!(Some(7), e.column());
+                }
+                _ => panic!("Syntax error expected"),
+            }
+        }
+    }
+
+    let value = "{{#if foo}}{{/each}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Tag name mismatch error expected"),
+        Err(e) => {
+            let context = e.source_context().unwrap();
+            assert_eq!(1, context.line());
+            assert_eq!(&["opening name is 'if'".to_string()], context.notes());
+        }
+    }
+
+    let e = Error::TemplateNotFound("foo".to_string());
+    assert!(e.source_context().is_none());
+
+    let e = SyntaxError::ExpectedPath("custom".to_string().into());
+    assert_eq!("custom", e.snippet().as_str());
+    assert!(e.source_context().is_none());
+    assert!(e.line().is_none());
+    Ok(())
+}
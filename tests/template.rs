@@ -0,0 +1,46 @@
+use bracket::{
+    parser::ast::{Node, Slice},
+    Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "template.rs";
+
+#[test]
+fn template_walk() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{> header}}{{#if foo}}{{bar}}{{else if qux}}{{> body}}{{else}}{{baz}}{{/if}}";
+    let template = registry.parse(NAME, value)?;
+    let mut calls = Vec::new();
+    template.walk(|node| match node {
+        Node::Statement(call) => calls.push(call.as_str().to_string()),
+        Node::Block(block) => calls.push(block.call().as_str().to_string()),
+        _ => {}
+    });
+    assert_eq!(
+        vec![
+            "{{> header}}",
+            "{{#if foo}}",
+            "{{bar}}",
+            "{{else if qux}}",
+            "{{> body}}",
+            "{{else}}",
+            "{{baz}}",
+        ],
+        calls
+    );
+    Ok(())
+}
+
+#[test]
+fn template_render_owned() -> Result<()> {
+    let registry = Registry::new();
+    let template = registry.parse(NAME, String::from("Hello {{name}}"))?;
+    let result =
+        registry.render_template(NAME, &template, &json!({"name": "foo"}))?;
+    assert_eq!("Hello foo", &result);
+    let result =
+        registry.render_template(NAME, &template, &json!({"name": "bar"}))?;
+    assert_eq!("Hello bar", &result);
+    Ok(())
+}
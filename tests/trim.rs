@@ -124,3 +124,131 @@ bar
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn trim_standalone_block() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        "<ul>\n  {{#each items}}\n  <li>{{this}}</li>\n  {{/each}}\n</ul>\n";
+    let data = json!({"items": ["a", "b"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<ul>\n  <li>a</li>\n  <li>b</li>\n</ul>\n", &result);
+    Ok(())
+}
+
+#[test]
+fn trim_standalone_else() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{#if foo}}\nfoo\n  {{else}}  \nbar\n{{/if}}\nqux";
+    let data = json!({"foo": false});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("bar\nqux", &result);
+    Ok(())
+}
+
+#[test]
+fn trim_standalone_comment() -> Result<()> {
+    let registry = Registry::new();
+    let value = "foo\n  {{! comment }}\nbar\n{{!-- comment --}}\r\nqux";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("foo\nbar\nqux", &result);
+    Ok(())
+}
+
+#[test]
+fn trim_standalone_partial() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("foo", "bar\n")?;
+    registry.insert("list", "<ul>\n  {{> item}}\n</ul>\n")?;
+    registry.insert("item", "<li>{{name}}</li>\n")?;
+    let data = json!({"name": "a\nb"});
+    let cases = [
+        ("<div>\n  {{> foo}}\n</div>", "<div>\n  bar\n</div>"),
+        ("<div>\n{{> foo}}\n</div>", "<div>\nbar\n</div>"),
+        ("<div>\n\t{{> foo}}\r\n</div>", "<div>\n\tbar\n</div>"),
+        (
+            "<div>\n  {{> list}}\n</div>",
+            "<div>\n  <ul>\n    <li>a\n    b</li>\n  </ul>\n</div>",
+        ),
+        ("<div>  {{> foo}}\n</div>", "<div>  bar\n\n</div>"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn trim_standalone_whitespace() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    let cases = [
+        ("a\n \t{{! comment }}\t \nb", "a\nb"),
+        ("a\n{{! comment }}\r\nb", "a\nb"),
+        ("a\n\u{a0}{{! comment }}\nb", "a\n\u{a0}\nb"),
+        ("a\n{{! comment }}\u{a0}\nb", "a\n\u{a0}\nb"),
+        ("a\n{{! comment }}\r\rb", "a\n\r\rb"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{:?}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn trim_standalone_inline() -> Result<()> {
+    let registry = Registry::new();
+    let value = "foo {{#if true}}bar{{/if}} {{! comment }} baz\n";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("foo bar  baz\n", &result);
+    Ok(())
+}
+
+#[test]
+fn trim_else_boundary() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"[{{#if a~}} X {{~else~}} Y {{~/if}}]";
+    for (a, expected) in [(true, "[X]"), (false, "[Y]")].iter() {
+        let result = registry.once(NAME, value, &json!({ "a": a }))?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn trim_else_markers() -> Result<()> {
+    let registry = Registry::new();
+    let cases = [
+        (r"[{{#if a}} X {{~else}} Y {{/if}}]", true, "[ X]"),
+        (r"[{{#if a}} X {{~else}} Y {{/if}}]", false, "[ Y ]"),
+        (r"[{{#if a}} X {{else~}} Y {{/if}}]", true, "[ X ]"),
+        (r"[{{#if a}} X {{else~}} Y {{/if}}]", false, "[Y ]"),
+        (r"[{{#if a}} X {{/if~}} ]", false, "[]"),
+        (r"[{{#each a~}} X {{~else~}} Y {{~/each}}]", false, "[Y]"),
+    ];
+    for (value, a, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &json!({ "a": a }))?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn trim_else_if_boundary() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"[{{#if a}} X {{~else if b~}} Z {{~else~}} Y {{/if~}} ]";
+    let cases = [
+        (json!({"a": true, "b": false}), "[ X]"),
+        (json!({"a": false, "b": true}), "[Z]"),
+        (json!({"a": false, "b": false}), "[Y ]"),
+    ];
+    for (data, expected) in cases.iter() {
+        let result = registry.once(NAME, value, data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "types.rs";
+
+#[test]
+fn types_predicates() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "list": [1],
+        "map": {},
+        "text": "foo",
+        "num": 1.5,
+        "flag": false,
+        "nothing": null,
+    });
+    let cases = [
+        (r"{{is_array list}}{{is_array map}}", "truefalse"),
+        (r"{{is_object map}}{{is_object list}}", "truefalse"),
+        (r"{{is_string text}}{{is_string num}}", "truefalse"),
+        (r"{{is_number num}}{{is_number text}}", "truefalse"),
+        (r"{{is_bool flag}}{{is_bool nothing}}", "truefalse"),
+        (
+            r"{{is_null nothing}}{{is_null missing}}{{is_null flag}}",
+            "truetruefalse",
+        ),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn types_branch() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if (is_array foo)}}{{#each foo}}[{{this}}]{{/each}}{{else}}{{foo}}{{/if}}";
+    let result = registry.once(NAME, value, &json!({"foo": ["a", "b"]}))?;
+    assert_eq!("[a][b]", &result);
+    let result = registry.once(NAME, value, &json!({"foo": "a"}))?;
+    assert_eq!("a", &result);
+    Ok(())
+}
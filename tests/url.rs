@@ -0,0 +1,47 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "url.rs";
+
+#[test]
+fn url_encode() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"query": "a b&c=d/é~*"});
+    let cases = [
+        (r"{{urlencode query}}", "a%20b%26c%3Dd%2F%C3%A9%7E*"),
+        (r"{{urlencode query plus=true}}", "a+b%26c%3Dd%2F%C3%A9%7E*"),
+        (r#"{{urlencode "safe-name_1.txt"}}"#, "safe-name_1.txt"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn url_decode() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"encoded": "a+b%20c%C3%A9%zz%+1%"});
+    let cases = [
+        (r"{{urldecode encoded}}", "a+b cé%zz%+1%"),
+        (r"{{urldecode encoded plus=true}}", "a b cé%zz% 1%"),
+        (r"{{urldecode (urlencode encoded)}}", "a+b%20c%C3%A9%zz%+1%"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    Ok(())
+}
+
+#[test]
+fn url_invalid() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    assert!(registry.once(NAME, r"{{urlencode 1}}", &data).is_err());
+    assert!(registry
+        .once(NAME, r#"{{urldecode "%FF"}}"#, &data)
+        .is_err());
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use bracket::{Registry, Result};
+use bracket::{parser::ast::Node, Registry, Result};
 use serde_json::json;
 
 const NAME: &str = "vars.rs";
@@ -43,6 +43,47 @@ fn vars_this() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn vars_this_each() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "arr": ["a", "b"],
+        "nums": [1, 2.5, true, null],
+        "nested": [["a", "b"], ["c"]],
+    });
+    let cases = [
+        (r"{{#each arr}}{{this}}{{/each}}", "ab"),
+        (r"{{#each nums}}{{this}},{{/each}}", "1,2.5,true,null,"),
+        (
+            r"{{#each nested}}{{#each this}}{{this}}{{/each}}{{/each}}",
+            "abc",
+        ),
+        (r"{{#each nested}}{{this}} {{/each}}", "Array[2] Array[1] "),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
+#[test]
+fn vars_this_root() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"item": {"title": "foo"}, "list": [1, 2, 3]});
+    let cases = [
+        (r"{{this}}", "Object"),
+        (r"{{#with item}}{{this}}{{/with}}", "Object"),
+        (r"{{#with list}}{{this}}{{/with}}", "Array[3]"),
+        (r"{{#each item}}{{this}}{{/each}}", "foo"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result, "{}", value);
+    }
+    Ok(())
+}
+
 #[test]
 fn vars_this_dot_slash() -> Result<()> {
     let registry = Registry::new();
@@ -204,3 +245,53 @@ fn vars_scope_explicit_this_no_inherit() -> Result<()> {
     assert_eq!("", &result);
     Ok(())
 }
+
+#[test]
+fn vars_escaped_statement_call() -> Result<()> {
+    let registry = Registry::new();
+    let template = registry.parse(NAME, r"{{foo}}{{{foo}}}{{{~foo~}}}")?;
+    let escaped: Vec<bool> = template
+        .node()
+        .into_iter()
+        .map(|node| match node {
+            Node::Statement(call) => call.is_escaped(),
+            _ => panic!("Expecting statement node"),
+        })
+        .collect();
+    assert_eq!(vec![true, false, false], escaped);
+    Ok(())
+}
+
+#[test]
+fn vars_absolute_path() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#with a}}{{{json /a/b}}} {{lookup /c "d"}}{{/with}}"#;
+    let data = json!({"a": {"b": "foo", "a": {"b": "bar"}}, "c": {"d": "qux"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#""foo" qux"#, &result);
+    Ok(())
+}
+
+#[test]
+fn vars_negative_index() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({"items": ["a", "b", "c"], "grid": [["x"], ["y", "z"]]});
+    let cases = [
+        (r"{{items.-1}}", "c"),
+        (r"{{items.-3}}", "a"),
+        (r"{{items.[-2]}}", "b"),
+        (r"{{items.-4}}", ""),
+        (r"{{lookup items -1}}", "c"),
+        (r#"{{lookup items "-2"}}"#, "b"),
+        (r"{{items.1}}", "b"),
+        (r"{{grid.1.0}}", "y"),
+        (r"{{grid.1.-1}}", "z"),
+    ];
+    for (value, expected) in cases.iter() {
+        let result = registry.once(NAME, value, &data)?;
+        assert_eq!(expected, &result);
+    }
+    registry.set_strict(true);
+    assert!(registry.once(NAME, r"{{items.-4}}", &data).is_err());
+    Ok(())
+}
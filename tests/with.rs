@@ -0,0 +1,54 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "with.rs";
+
+#[test]
+fn with_object() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#with user}}{{name}}{{else}}No user{{/with}}";
+    let data = json!({"user": {"name": "foo"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("foo", &result);
+    Ok(())
+}
+
+#[test]
+fn with_null_else() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#with user}}{{name}}{{else}}No user{{/with}}";
+    let data = json!({"user": null});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("No user", &result);
+    Ok(())
+}
+
+#[test]
+fn with_missing_else() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#with user}}{{name}}{{else}}No {{kind}}{{/with}}";
+    let data = json!({"kind": "user"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("No user", &result);
+    Ok(())
+}
+
+#[test]
+fn with_null_no_else() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"[{{#with user}}{{name}}{{/with}}]";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[]", &result);
+    Ok(())
+}
+
+#[test]
+fn with_block_params() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#with user as |author|}}{{author.name}}:{{name}}{{/with}}";
+    let data = json!({"user": {"name": "foo"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("foo:foo", &result);
+    Ok(())
+}